@@ -0,0 +1,423 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use crate::clock::Clock;
+use crate::difficulty::Settings;
+use crate::game::{Game, Outcome, Status};
+
+pub const DEFAULT_PORT: u16 = 8080;
+
+// games nobody has touched for this long are dropped
+pub const DEFAULT_TTL: Duration = Duration::from_secs(10 * 60);
+
+// requests are tiny; anything bigger than this is a mistake or an attack
+const MAX_BODY: usize = 64 * 1024;
+
+// the request line and headers together, for the same reason
+const MAX_HEADERS: usize = 8 * 1024;
+
+// a client that stops sending mid-request is dropped after this long instead of holding a thread
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+// the body of POST /games. anything left out comes from the server's own settings.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NewGame {
+    low: Option<i128>,
+    high: Option<i128>,
+    // 0 means unlimited, as with --attempts
+    max_attempts: Option<u32>,
+    seed: Option<u64>,
+}
+
+// the body of POST /games/{id}/guesses
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NewGuess {
+    guess: i128,
+}
+
+#[derive(Debug, Serialize)]
+struct GuessView {
+    guess: i128,
+    // "Less", "Greater" or "Equal", named after std::cmp::Ordering
+    ordering: &'static str,
+}
+
+// what GET /games/{id} shows
+#[derive(Debug, Serialize)]
+struct GameView {
+    id: String,
+    low: i128,
+    high: i128,
+    max_attempts: Option<u32>,
+    seed: u64,
+    status: &'static str,
+    attempts: u32,
+    attempts_left: Option<u32>,
+    history: Vec<GuessView>,
+    // only once the game is over
+    #[serde(skip_serializing_if = "Option::is_none")]
+    secret: Option<i128>,
+}
+
+// what POST /games/{id}/guesses answers
+#[derive(Debug, Serialize)]
+struct GuessReply {
+    guess: i128,
+    ordering: &'static str,
+    status: &'static str,
+    attempts: u32,
+    attempts_left: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+struct ErrorReply {
+    error: String,
+}
+
+fn ordering_name(ordering: Ordering) -> &'static str {
+    match ordering {
+        Ordering::Less => "Less",
+        Ordering::Greater => "Greater",
+        Ordering::Equal => "Equal",
+    }
+}
+
+fn status_name(status: Status) -> &'static str {
+    match status {
+        Status::Playing => "playing",
+        Status::Won => "won",
+        Status::Lost => "lost",
+        Status::GaveUp => "gave_up",
+        Status::Quit => "quit",
+    }
+}
+
+struct Session {
+    game: Game,
+    seed: u64,
+    // clock time of the last request that touched this game
+    last_used: Duration,
+}
+
+impl Session {
+    fn view(&self, id: &str) -> GameView {
+        let game = &self.game;
+        let settings = game.settings();
+        GameView {
+            id: id.to_string(),
+            low: settings.low,
+            high: settings.high,
+            max_attempts: settings.max_attempts,
+            seed: self.seed,
+            status: status_name(game.status()),
+            attempts: game.attempts(),
+            attempts_left: game.attempts_left(),
+            history: game
+                .history()
+                .iter()
+                .map(|(guess, ordering)| GuessView {
+                    guess: *guess,
+                    ordering: ordering_name(*ordering),
+                })
+                .collect(),
+            secret: if game.is_finished() { Some(game.secret()) } else { None },
+        }
+    }
+}
+
+// every live game, shared between the connection threads
+struct Store<C> {
+    settings: Settings,
+    ttl: Duration,
+    clock: C,
+    // draws seeds for games that don't ask for one, and the game ids
+    rng: StdRng,
+    sessions: HashMap<String, Session>,
+}
+
+impl<C: Clock> Store<C> {
+    // expiry is checked whenever a request comes in, so an idle server needs no timer thread
+    fn expire(&mut self) {
+        let now = self.clock.elapsed();
+        let ttl = self.ttl;
+        self.sessions.retain(|_, session| now.saturating_sub(session.last_used) < ttl);
+    }
+
+    fn create(&mut self, request: NewGame) -> Response {
+        let mut settings = self.settings;
+        if let Some(low) = request.low {
+            settings.low = low;
+        }
+        if let Some(high) = request.high {
+            settings.high = high;
+        }
+        if let Some(max) = request.max_attempts {
+            settings.max_attempts = if max == 0 { None } else { Some(max) };
+        }
+        if let Err(e) = settings.validate() {
+            return Response::error(422, &e);
+        }
+
+        // the same seed gives the same secret as `guessing_game --seed`
+        let seed = request.seed.unwrap_or_else(|| self.rng.gen());
+        let game = Game::new(settings, &mut StdRng::seed_from_u64(seed));
+
+        let id = loop {
+            let id = format!("{:016x}", self.rng.gen::<u64>());
+            if !self.sessions.contains_key(&id) {
+                break id;
+            }
+        };
+        let session = Session {
+            game,
+            seed,
+            last_used: self.clock.elapsed(),
+        };
+        let response =
+            Response::json(201, &session.view(&id)).with_location(format!("/games/{}", id));
+        self.sessions.insert(id, session);
+        response
+    }
+
+    fn session(&mut self, id: &str) -> Result<&mut Session, Response> {
+        let now = self.clock.elapsed();
+        match self.sessions.get_mut(id) {
+            Some(session) => {
+                session.last_used = now;
+                Ok(session)
+            }
+            None => Err(Response::error(404, "no such game; it may have expired")),
+        }
+    }
+
+    fn show(&mut self, id: &str) -> Response {
+        match self.session(id) {
+            Ok(session) => Response::json(200, &session.view(id)),
+            Err(response) => response,
+        }
+    }
+
+    fn guess(&mut self, id: &str, request: NewGuess) -> Response {
+        let session = match self.session(id) {
+            Ok(session) => session,
+            Err(response) => return response,
+        };
+        let game = &mut session.game;
+
+        if game.is_finished() {
+            return Response::error(409, "the game is already over");
+        }
+        if !game.settings().contains(request.guess) {
+            let settings = game.settings();
+            return Response::error(
+                422,
+                &format!("the guess must be between {} and {}", settings.low, settings.high),
+            );
+        }
+
+        match game.submit(request.guess) {
+            Outcome::Guess(ordering) => Response::json(
+                200,
+                &GuessReply {
+                    guess: request.guess,
+                    ordering: ordering_name(ordering),
+                    status: status_name(game.status()),
+                    attempts: game.attempts(),
+                    attempts_left: game.attempts_left(),
+                },
+            ),
+            Outcome::Finished => Response::error(409, "the game is already over"),
+        }
+    }
+
+    fn handle(&mut self, request: &Request) -> Response {
+        self.expire();
+
+        let path = request.path.split('?').next().unwrap_or("");
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+        match (request.method.as_str(), segments.as_slice()) {
+            ("POST", ["games"]) => match parse_body::<NewGame>(&request.body) {
+                Ok(new_game) => self.create(new_game),
+                Err(response) => response,
+            },
+            ("GET", ["games", id]) => self.show(id),
+            ("POST", ["games", id, "guesses"]) => match parse_body::<NewGuess>(&request.body) {
+                Ok(guess) => self.guess(id, guess),
+                Err(response) => response,
+            },
+            (_, ["games"]) | (_, ["games", _]) | (_, ["games", _, "guesses"]) => {
+                Response::error(405, "method not allowed")
+            }
+            _ => Response::error(404, "not found"),
+        }
+    }
+}
+
+// an empty body is the same as {}
+fn parse_body<T: DeserializeOwned>(body: &[u8]) -> Result<T, Response> {
+    let body = if body.iter().all(|b| b.is_ascii_whitespace()) { b"{}" } else { body };
+    serde_json::from_slice(body).map_err(|e| Response::error(400, &format!("invalid JSON: {}", e)))
+}
+
+#[derive(Debug)]
+struct Request {
+    method: String,
+    path: String,
+    body: Vec<u8>,
+}
+
+#[derive(Debug)]
+struct Response {
+    status: u16,
+    location: Option<String>,
+    body: String,
+}
+
+impl Response {
+    fn json<T: Serialize>(status: u16, body: &T) -> Response {
+        Response {
+            status,
+            location: None,
+            body: serde_json::to_string(body).unwrap_or_else(|_| String::from("{}")),
+        }
+    }
+
+    fn error(status: u16, message: &str) -> Response {
+        Response::json(
+            status,
+            &ErrorReply {
+                error: message.to_string(),
+            },
+        )
+    }
+
+    fn with_location(self, location: String) -> Response {
+        Response {
+            location: Some(location),
+            ..self
+        }
+    }
+
+    fn write_to<W: Write>(&self, output: &mut W) -> io::Result<()> {
+        let reason = match self.status {
+            200 => "OK",
+            201 => "Created",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            409 => "Conflict",
+            413 => "Payload Too Large",
+            422 => "Unprocessable Entity",
+            431 => "Request Header Fields Too Large",
+            _ => "Internal Server Error",
+        };
+        write!(output, "HTTP/1.1 {} {}\r\n", self.status, reason)?;
+        write!(output, "Content-Type: application/json\r\n")?;
+        write!(output, "Content-Length: {}\r\n", self.body.len())?;
+        if let Some(location) = &self.location {
+            write!(output, "Location: {}\r\n", location)?;
+        }
+        // one request per connection keeps the server simple
+        write!(output, "Connection: close\r\n\r\n")?;
+        output.write_all(self.body.as_bytes())?;
+        output.flush()
+    }
+}
+
+// reads one request: the request line, the headers and a Content-Length body
+fn read_request<R: BufRead>(input: &mut R) -> Result<Request, Response> {
+    let bad = |message: &str| Response::error(400, message);
+    let too_large = || Response::error(431, "the request headers are too large");
+
+    // everything up to the body is read through one cap, so an endless line can't fill the memory
+    let mut head = input.take(MAX_HEADERS as u64);
+
+    let mut line = String::new();
+    head.read_line(&mut line).map_err(|_| bad("could not read the request"))?;
+    if head.limit() == 0 && !line.ends_with('\n') {
+        return Err(too_large());
+    }
+    let mut parts = line.split_whitespace();
+    let (method, path) = match (parts.next(), parts.next()) {
+        (Some(method), Some(path)) => (method.to_string(), path.to_string()),
+        _ => return Err(bad("malformed request line")),
+    };
+
+    let mut length = 0;
+    loop {
+        line.clear();
+        if head.read_line(&mut line).map_err(|_| bad("could not read the headers"))? == 0 {
+            return Err(bad("the headers ended early"));
+        }
+        if head.limit() == 0 && !line.ends_with('\n') {
+            return Err(too_large());
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse().map_err(|_| bad("invalid Content-Length"))?;
+            }
+        }
+    }
+
+    if length > MAX_BODY {
+        return Err(Response::error(413, "the body is too large"));
+    }
+    let mut body = vec![0; length];
+    input.read_exact(&mut body).map_err(|_| bad("the body ended early"))?;
+
+    Ok(Request { method, path, body })
+}
+
+fn handle_connection<C: Clock>(stream: TcpStream, store: Arc<Mutex<Store<C>>>) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut writer = stream.try_clone()?;
+    let mut reader = BufReader::new(stream);
+
+    let response = match read_request(&mut reader) {
+        Ok(request) => store.lock().unwrap().handle(&request),
+        Err(response) => response,
+    };
+    response.write_to(&mut writer)
+}
+
+// answers the JSON API on the listener until it fails. games take their defaults from settings
+// and are dropped once they have been idle for ttl, as measured by clock.
+pub fn serve<C: Clock + Send + 'static>(
+    listener: TcpListener,
+    settings: Settings,
+    seed: u64,
+    ttl: Duration,
+    clock: C,
+) -> io::Result<()> {
+    let store = Arc::new(Mutex::new(Store {
+        settings,
+        ttl,
+        clock,
+        rng: StdRng::seed_from_u64(seed),
+        sessions: HashMap::new(),
+    }));
+
+    for stream in listener.incoming() {
+        let stream = stream?;
+        let store = Arc::clone(&store);
+        thread::spawn(move || {
+            let _ = handle_connection(stream, store);
+        });
+    }
+
+    Ok(())
+}
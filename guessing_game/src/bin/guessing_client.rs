@@ -0,0 +1,31 @@
+use std::env;
+use std::io;
+use std::net::TcpStream;
+use std::process;
+use guessing_game::{cli, multiplayer, storage};
+
+// joins a room on a guessing_game server: guessing_client [--addr host:port] [--room name] [--name player]
+fn main() {
+    let options = cli::parse_args(env::args().skip(1)).unwrap_or_else(|e| exit_with(&e));
+
+    let addr = options
+        .addr
+        .clone()
+        .unwrap_or_else(|| format!("127.0.0.1:{}", multiplayer::DEFAULT_PORT));
+    let room = options.room.clone().unwrap_or_else(|| String::from("lobby"));
+    let name = options.player.clone().unwrap_or_else(storage::default_player);
+
+    let stream = TcpStream::connect(&addr)
+        .unwrap_or_else(|e| exit_with(&format!("could not connect to {}: {}", addr, e)));
+
+    println!("Connected to {}. Type a number to guess, or :quit to leave.", addr);
+
+    let stdin = io::stdin();
+    multiplayer::run_client(stream, stdin.lock(), io::stdout(), &room, &name)
+        .unwrap_or_else(|e| exit_with(&e.to_string()));
+}
+
+fn exit_with(message: &str) -> ! {
+    eprintln!("guessing_client: {}", message);
+    process::exit(2);
+}
@@ -0,0 +1,422 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::time::Instant;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use crate::command;
+use crate::difficulty::Difficulty;
+use crate::game::Status;
+use crate::locale::Locale;
+use crate::{run_command, Commands, Summary};
+
+// a code can't be longer than the number of distinct digits
+pub const MAX_DIGITS: usize = 10;
+
+// the solver and :hint stop here: a 6 digit code has 151200 candidates, too many to weigh each turn
+pub const MAX_SOLVER_DIGITS: usize = 5;
+
+// checks that the solver can crack codes of this length
+pub fn validate_solver(digits: usize) -> Result<(), String> {
+    if digits == 0 || digits > MAX_SOLVER_DIGITS {
+        return Err(format!("the solver handles codes of 1 to {} digits", MAX_SOLVER_DIGITS));
+    }
+    Ok(())
+}
+
+// how long the code is and how many tries the player gets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BullsSettings {
+    pub difficulty: Difficulty,
+    pub digits: usize,
+    pub max_attempts: Option<u32>,
+}
+
+impl BullsSettings {
+    pub fn preset(difficulty: Difficulty) -> BullsSettings {
+        let digits = match difficulty {
+            Difficulty::Easy => 3,
+            Difficulty::Normal | Difficulty::Custom => 4,
+            Difficulty::Hard => 5,
+        };
+        BullsSettings {
+            difficulty,
+            digits,
+            max_attempts: Some(10),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.digits == 0 || self.digits > MAX_DIGITS {
+            return Err(format!("a code has between 1 and {} digits", MAX_DIGITS));
+        }
+        if self.max_attempts == Some(0) {
+            return Err(String::from("the attempt limit must be at least 1"));
+        }
+        Ok(())
+    }
+
+    // the codes as numbers run from 0 to 10^digits - 1, which is what goes on the leaderboard
+    pub fn range(&self) -> (i128, i128) {
+        (0, 10i128.pow(self.digits as u32) - 1)
+    }
+}
+
+// a sequence of distinct digits
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Code(Vec<u8>);
+
+// why a line is not a valid code
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvalidCode {
+    WrongLength { expected: usize, got: usize },
+    NotADigit(char),
+    Repeated(u8),
+}
+
+impl InvalidCode {
+    pub fn message(&self, locale: Locale) -> String {
+        match self {
+            InvalidCode::WrongLength { expected, got } => {
+                locale.format("code_wrong_length", &[("expected", expected), ("got", got)])
+            }
+            InvalidCode::NotADigit(c) => locale.format("code_not_a_digit", &[("text", c)]),
+            InvalidCode::Repeated(d) => locale.format("code_repeated", &[("digit", d)]),
+        }
+    }
+}
+
+impl fmt::Display for InvalidCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message(Locale::En))
+    }
+}
+
+impl Code {
+    pub fn random<G: Rng + ?Sized>(digits: usize, rng: &mut G) -> Code {
+        let mut all: Vec<u8> = (0..10).collect();
+        all.shuffle(rng);
+        all.truncate(digits);
+        Code(all)
+    }
+
+    pub fn parse(text: &str, digits: usize) -> Result<Code, InvalidCode> {
+        let text = text.trim();
+        let mut code = Vec::with_capacity(digits);
+
+        for c in text.chars() {
+            let digit = c.to_digit(10).ok_or(InvalidCode::NotADigit(c))? as u8;
+            if code.contains(&digit) {
+                return Err(InvalidCode::Repeated(digit));
+            }
+            code.push(digit);
+        }
+
+        if code.len() != digits {
+            return Err(InvalidCode::WrongLength {
+                expected: digits,
+                got: code.len(),
+            });
+        }
+        Ok(Code(code))
+    }
+
+    // every code of the given length, in increasing order
+    pub fn all(digits: usize) -> Vec<Code> {
+        let mut codes = Vec::new();
+        let mut current = Vec::with_capacity(digits);
+        fill(&mut codes, &mut current, digits);
+        codes
+    }
+
+    pub fn digits(&self) -> &[u8] {
+        &self.0
+    }
+
+    // one bit per digit in the code
+    fn mask(&self) -> u16 {
+        self.0.iter().fold(0, |mask, &digit| mask | 1 << digit)
+    }
+
+    pub fn score(&self, guess: &Code) -> Score {
+        let bulls = self.0.iter().zip(&guess.0).filter(|(a, b)| a == b).count();
+        let shared = (self.mask() & guess.mask()).count_ones() as usize;
+        Score {
+            bulls,
+            cows: shared - bulls,
+        }
+    }
+}
+
+fn fill(codes: &mut Vec<Code>, current: &mut Vec<u8>, digits: usize) {
+    if current.len() == digits {
+        codes.push(Code(current.clone()));
+        return;
+    }
+    for digit in 0..10 {
+        if !current.contains(&digit) {
+            current.push(digit);
+            fill(codes, current, digits);
+            current.pop();
+        }
+    }
+}
+
+impl fmt::Display for Code {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for digit in &self.0 {
+            write!(f, "{}", digit)?;
+        }
+        Ok(())
+    }
+}
+
+// bulls are right digits in the right place, cows are right digits in the wrong place
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Score {
+    pub bulls: usize,
+    pub cows: usize,
+}
+
+impl Score {
+    pub fn message(&self, locale: Locale) -> String {
+        format!(
+            "{} {}, {} {}",
+            self.bulls,
+            locale.text(if self.bulls == 1 { "bull" } else { "bulls" }),
+            self.cows,
+            locale.text(if self.cows == 1 { "cow" } else { "cows" })
+        )
+    }
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message(Locale::En))
+    }
+}
+
+// Knuth's minimax idea applied to bulls and cows: guess whatever leaves the fewest candidates
+// in the worst case, preferring a guess that could itself be the secret
+#[derive(Debug, Clone)]
+pub struct Solver {
+    digits: usize,
+    all: Vec<Code>,
+    candidates: Vec<Code>,
+}
+
+impl Solver {
+    // above this many codes only the candidates are tried as guesses, to keep each turn quick
+    const FULL_SEARCH: usize = 5040;
+
+    // the most guess and candidate pairs scored in one turn; past it an evenly spaced sample of
+    // the guesses is tried instead of all of them
+    const BUDGET: usize = 2_000_000;
+
+    // digits must be at most MAX_SOLVER_DIGITS; see validate_solver
+    pub fn new(digits: usize) -> Solver {
+        let all = Code::all(digits);
+        Solver {
+            digits,
+            candidates: all.clone(),
+            all,
+        }
+    }
+
+    pub fn candidates(&self) -> &[Code] {
+        &self.candidates
+    }
+
+    pub fn next_guess(&self) -> Code {
+        // nothing is known yet, so every opening is as good as 0123...
+        if self.candidates.len() == self.all.len() {
+            return Code((0..self.digits as u8).collect());
+        }
+        if self.candidates.len() <= 2 {
+            return self.candidates[0].clone();
+        }
+
+        let pool = if self.all.len() <= Solver::FULL_SEARCH {
+            &self.all
+        } else {
+            &self.candidates
+        };
+        let step = (pool.len() * self.candidates.len()).div_ceil(Solver::BUDGET).max(1);
+
+        let possible: HashSet<&Code> = self.candidates.iter().collect();
+        let mut best = &self.candidates[0];
+        let mut best_key = (usize::MAX, true);
+        for guess in pool.iter().step_by(step) {
+            // one counter for each score, indexed by bulls * 11 + cows
+            let mut counts = [0usize; 121];
+            for candidate in &self.candidates {
+                let score = candidate.score(guess);
+                counts[score.bulls * 11 + score.cows] += 1;
+            }
+            let worst = counts.iter().copied().max().unwrap_or(0);
+            let key = (worst, !possible.contains(guess));
+            if key < best_key {
+                best_key = key;
+                best = guess;
+            }
+        }
+        best.clone()
+    }
+
+    // keeps only the codes that would have given the same score
+    pub fn record(&mut self, guess: &Code, score: Score) {
+        self.candidates.retain(|candidate| candidate.score(guess) == score);
+    }
+}
+
+// a game of bulls and cows in progress
+struct Round {
+    digits: usize,
+    secret: Code,
+    history: Vec<(Code, Score)>,
+    // only built on the first hint, since it starts from every possible code
+    solver: Option<Solver>,
+    status: Status,
+}
+
+impl Commands for Round {
+    fn quit(&mut self) {
+        self.status = Status::Quit;
+    }
+
+    fn give_up(&mut self, locale: Locale) -> String {
+        self.status = Status::GaveUp;
+        locale.format("bulls_gave_up", &[("attempts", &self.history.len()), ("code", &self.secret)])
+    }
+
+    fn hint(&mut self, locale: Locale) -> String {
+        if validate_solver(self.digits).is_err() {
+            return locale.format("bulls_no_hints", &[("digits", &MAX_SOLVER_DIGITS)]);
+        }
+        // the solver replays the history when it is built
+        let (digits, history) = (self.digits, &self.history);
+        let solver = self.solver.get_or_insert_with(|| {
+            let mut solver = Solver::new(digits);
+            for (guess, score) in history {
+                solver.record(guess, *score);
+            }
+            solver
+        });
+        locale.format("bulls_hint", &[("count", &solver.candidates().len()), ("guess", &solver.next_guess())])
+    }
+
+    fn history(&self, locale: Locale) -> Vec<String> {
+        self.history
+            .iter()
+            .enumerate()
+            .map(|(i, (guess, score))| format!("{:>3}. {}  {}", i + 1, guess, score.message(locale)))
+            .collect()
+    }
+}
+
+// plays bulls and cows with the same commands and input handling as the number game.
+// :hint asks the solver for a suggestion.
+pub fn bulls_and_cows<R: BufRead, W: Write, G: Rng>(
+    mut input: R,
+    mut output: W,
+    settings: BullsSettings,
+    rng: &mut G,
+    locale: Locale,
+) -> io::Result<Summary> {
+    let started = Instant::now();
+    let mut round = Round {
+        digits: settings.digits,
+        secret: Code::random(settings.digits, rng),
+        history: Vec::new(),
+        solver: None,
+        status: Status::Playing,
+    };
+
+    writeln!(output, "{}", locale.text("bulls_title"))?;
+    match settings.max_attempts {
+        Some(max) => writeln!(
+            output,
+            "{}",
+            locale.format("bulls_code_attempts", &[("digits", &settings.digits), ("attempts", &max)])
+        )?,
+        None => writeln!(output, "{}", locale.format("bulls_code", &[("digits", &settings.digits)]))?,
+    }
+    writeln!(output, "{}", locale.text("help_hint"))?;
+
+    while round.status == Status::Playing {
+        writeln!(output, "{}", locale.text("prompt"))?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            round.status = Status::Quit;
+            writeln!(output, "{}", locale.format("bulls_no_more_input", &[("code", &round.secret)]))?;
+            break;
+        }
+
+        if let Some(command) = command::parse_command(&line) {
+            match command {
+                Ok(command) => run_command(&mut output, &mut round, command, locale)?,
+                Err(unknown) => writeln!(output, "{}", unknown.message(locale))?,
+            }
+            continue;
+        }
+
+        let guess = match Code::parse(&line, settings.digits) {
+            Ok(code) => code,
+            Err(invalid) => {
+                writeln!(output, "{}", invalid.message(locale))?;
+                continue;
+            }
+        };
+
+        let score = round.secret.score(&guess);
+        writeln!(output, "{}", locale.format("you_guessed", &[("guess", &guess)]))?;
+        if let Some(solver) = round.solver.as_mut() {
+            solver.record(&guess, score);
+        }
+        round.history.push((guess, score));
+
+        if score.bulls == settings.digits {
+            round.status = Status::Won;
+            writeln!(output, "{}", locale.text("you_win"))?;
+        } else {
+            writeln!(output, "{}", score.message(locale))?;
+            if settings.max_attempts.is_some_and(|max| round.history.len() as u32 >= max) {
+                round.status = Status::Lost;
+                writeln!(output, "{}", locale.format("bulls_out_of_attempts", &[("code", &round.secret)]))?;
+            }
+        }
+    }
+
+    Ok(Summary {
+        status: round.status,
+        attempts: round.history.len() as u32,
+        secret: round.secret.to_string().parse().unwrap_or(0),
+        elapsed: started.elapsed(),
+    })
+}
+
+// the solver cracking a random code, narrating each guess. returns how many guesses it took.
+pub fn solve_game<W: Write, G: Rng>(mut output: W, digits: usize, rng: &mut G, locale: Locale) -> io::Result<u32> {
+    let secret = Code::random(digits, rng);
+    let mut solver = Solver::new(digits);
+    let mut guesses = 0;
+
+    writeln!(output, "{}", locale.format("bulls_solver_start", &[("digits", &digits)]))?;
+    loop {
+        let guess = solver.next_guess();
+        let score = secret.score(&guess);
+        guesses += 1;
+        writeln!(output, "{}  {}", guess, score.message(locale))?;
+
+        if score.bulls == digits {
+            writeln!(
+                output,
+                "{}",
+                locale.format("bulls_solver_found", &[("code", &guess), ("guesses", &guesses)])
+            )?;
+            return Ok(guesses);
+        }
+        solver.record(&guess, score);
+    }
+}
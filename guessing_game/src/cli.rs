@@ -0,0 +1,377 @@
+use std::env;
+use crate::bulls::BullsSettings;
+use crate::config::{ColorChoice, Config};
+use crate::decimal::DecimalSettings;
+use crate::difficulty::{Difficulty, Settings};
+use crate::locale::Locale;
+
+// the environment variable that pins the seed when --seed is not given
+pub const SEED_ENV: &str = "GUESSING_GAME_SEED";
+
+// what the binary was asked to do
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Command {
+    #[default]
+    Play,
+    // print the leaderboard without playing
+    Leaderboard,
+    // the player thinks of a number and the computer guesses it
+    Reverse,
+    // let the strategies play many games and report how they did
+    Simulate,
+    // watch the liar-mode solver find a number despite lies
+    Solve,
+    // host multiplayer rooms on a local port
+    Serve,
+    // several players taking turns at one terminal
+    HotSeat,
+    // crack a code of distinct digits from bulls and cows
+    Bulls,
+    // find as many secrets as possible against the clock
+    TimeAttack,
+    // play back a logged game
+    Replay,
+    // answer the JSON API over local HTTP
+    Api,
+    // per-player statistics from the history of finished games
+    Stats,
+    // guess a secret with digits after the decimal point
+    Decimal,
+    // print the settings in effect and where each came from
+    ConfigShow,
+}
+
+// everything the binary can be told on the command line
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Options {
+    pub command: Command,
+    // the name recorded on the leaderboard and in the history; stats shows only this player
+    pub player: Option<String>,
+    pub seed: Option<u64>,
+    // continue the game saved by :save or Ctrl-C
+    pub resume: bool,
+    // play full-screen instead of line by line
+    pub tui: bool,
+    // the language of the number game; None follows LANG
+    pub lang: Option<Locale>,
+    // when to colour the replies; None follows NO_COLOR
+    pub color: Option<ColorChoice>,
+    // the config file to read instead of the default one
+    pub config: Option<String>,
+    // the config file's values; its range is used when no range flag is given
+    pub defaults: Config,
+    // today's shared puzzle instead of a random secret
+    pub daily: bool,
+    // pick the range and attempts from the player's rating
+    pub adaptive: bool,
+    // the event log a game writes, or the one `replay` reads
+    pub log: Option<String>,
+    // replay: how many times faster than real time; 0 doesn't wait
+    pub speed: Option<f64>,
+    // api: seconds a game may sit idle before it is dropped
+    pub ttl: Option<u64>,
+    pub difficulty: Option<Difficulty>,
+    pub low: Option<i128>,
+    pub high: Option<i128>,
+    // 0 means unlimited attempts
+    pub attempts: Option<u32>,
+    // warmer/colder feedback after each guess
+    pub temperature: bool,
+    // liar mode: how many Too small!/Too big! replies may be lies
+    pub lies: Option<u32>,
+    // the port `serve` listens on
+    pub port: Option<u16>,
+    // the server the client connects to, as host:port
+    pub addr: Option<String>,
+    // the multiplayer room to join
+    pub room: Option<String>,
+    // hot seat players, in turn order
+    pub players: Vec<String>,
+    // hot seat: everyone guesses the same secret
+    pub shared: bool,
+    // hot seat: how many rounds the match is the best of
+    pub rounds: Option<u32>,
+    // bulls and cows: how many digits the code has
+    pub digits: Option<usize>,
+    // bulls and cows: watch the solver instead of playing
+    pub solver: bool,
+    // decimal mode: how many digits after the point
+    pub places: Option<u32>,
+    // time attack: the budget in seconds
+    pub time: Option<u64>,
+    // how many games `simulate` plays per strategy
+    pub games: Option<usize>,
+    // the strategies `simulate` runs; empty means all of them
+    pub strategies: Vec<String>,
+}
+
+// parses the arguments that follow the program name.
+// both "--seed 42" and "--seed=42" are accepted.
+pub fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Options, String> {
+    let mut options = Options::default();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+            None => (arg, None),
+        };
+
+        match flag.as_str() {
+            "--leaderboard" | "leaderboard" => options.command = Command::Leaderboard,
+            "--reverse" | "reverse" => options.command = Command::Reverse,
+            "--hot-cold" => options.temperature = true,
+            "--lies" => {
+                let value = flag_value(&flag, inline, &mut args)?;
+                options.lies = Some(parse_number(&flag, &value)?);
+            }
+            "solve" => options.command = Command::Solve,
+            "serve" => options.command = Command::Serve,
+            "--port" => {
+                let value = flag_value(&flag, inline, &mut args)?;
+                options.port = Some(parse_number(&flag, &value)?);
+            }
+            "--addr" => options.addr = Some(flag_value(&flag, inline, &mut args)?),
+            "--room" => options.room = Some(flag_value(&flag, inline, &mut args)?),
+            "hotseat" => options.command = Command::HotSeat,
+            "--players" => {
+                let value = flag_value(&flag, inline, &mut args)?;
+                options.players = value
+                    .split(',')
+                    .map(|name| name.trim().to_string())
+                    .filter(|name| !name.is_empty())
+                    .collect();
+            }
+            "--shared" => options.shared = true,
+            "--rounds" => {
+                let value = flag_value(&flag, inline, &mut args)?;
+                options.rounds = Some(parse_number(&flag, &value)?);
+            }
+            "bulls" => options.command = Command::Bulls,
+            "--digits" => {
+                let value = flag_value(&flag, inline, &mut args)?;
+                options.digits = Some(parse_number(&flag, &value)?);
+            }
+            "--solver" => options.solver = true,
+            "decimal" => options.command = Command::Decimal,
+            "--places" => {
+                let value = flag_value(&flag, inline, &mut args)?;
+                options.places = Some(parse_number(&flag, &value)?);
+            }
+            "timeattack" => options.command = Command::TimeAttack,
+            "--time" => {
+                let value = flag_value(&flag, inline, &mut args)?;
+                options.time = Some(parse_number(&flag, &value)?);
+            }
+            "simulate" => options.command = Command::Simulate,
+            "--games" => {
+                let value = flag_value(&flag, inline, &mut args)?;
+                options.games = Some(parse_number(&flag, &value)?);
+            }
+            "--strategy" => options.strategies.push(flag_value(&flag, inline, &mut args)?),
+            "--resume" => options.resume = true,
+            "--tui" => options.tui = true,
+            "--lang" => options.lang = Some(flag_value(&flag, inline, &mut args)?.parse()?),
+            "--color" | "--colour" => options.color = Some(flag_value(&flag, inline, &mut args)?.parse()?),
+            "--config" => options.config = Some(flag_value(&flag, inline, &mut args)?),
+            "config" => match args.next().as_deref() {
+                Some("show") => options.command = Command::ConfigShow,
+                Some(other) => return Err(format!("unknown config command: {} (expected show)", other)),
+                None => return Err(String::from("config needs a command: config show")),
+            },
+            "--daily" => options.daily = true,
+            "--adaptive" => options.adaptive = true,
+            "replay" => options.command = Command::Replay,
+            "api" => options.command = Command::Api,
+            "stats" => options.command = Command::Stats,
+            "--ttl" => {
+                let value = flag_value(&flag, inline, &mut args)?;
+                options.ttl = Some(parse_number(&flag, &value)?);
+            }
+            "--log" => options.log = Some(flag_value(&flag, inline, &mut args)?),
+            "--speed" => {
+                let value = flag_value(&flag, inline, &mut args)?;
+                let speed: f64 = parse_number(&flag, &value)?;
+                if !speed.is_finite() || speed < 0.0 {
+                    return Err(format!("invalid value for --speed: {}", value));
+                }
+                options.speed = Some(speed);
+            }
+            "--name" => options.player = Some(flag_value(&flag, inline, &mut args)?),
+            "--seed" => {
+                let value = flag_value(&flag, inline, &mut args)?;
+                options.seed = Some(parse_seed(&value)?);
+            }
+            "--difficulty" => {
+                let value = flag_value(&flag, inline, &mut args)?;
+                options.difficulty = Some(value.parse()?);
+            }
+            "--min" => {
+                let value = flag_value(&flag, inline, &mut args)?;
+                options.low = Some(parse_number(&flag, &value)?);
+            }
+            "--max" => {
+                let value = flag_value(&flag, inline, &mut args)?;
+                options.high = Some(parse_number(&flag, &value)?);
+            }
+            "--attempts" => {
+                let value = flag_value(&flag, inline, &mut args)?;
+                options.attempts = Some(parse_number(&flag, &value)?);
+            }
+            _ => return Err(format!("unknown argument: {}", flag)),
+        }
+    }
+
+    // --daily is played before --adaptive is ever looked at, so the clash has to be caught here
+    if options.daily && options.adaptive {
+        return Err(String::from("--adaptive can't be combined with --daily"));
+    }
+
+    Ok(options)
+}
+
+// the seed to play with: --seed wins over the environment, and a fresh random seed is used otherwise
+pub fn resolve_seed(options: &Options) -> Result<u64, String> {
+    if let Some(seed) = options.seed {
+        return Ok(seed);
+    }
+
+    match env::var(SEED_ENV) {
+        Ok(value) => parse_seed(&value).map_err(|e| format!("{} ({})", e, SEED_ENV)),
+        Err(_) => Ok(rand::random()),
+    }
+}
+
+impl Options {
+    // --lang if given, otherwise whatever the environment asks for
+    pub fn locale(&self) -> Locale {
+        self.lang.unwrap_or_else(Locale::from_env)
+    }
+
+    // whether any of the flags that pick the number game's range was given
+    pub fn range_given(&self) -> bool {
+        self.difficulty.is_some() || self.low.is_some() || self.high.is_some() || self.attempts.is_some()
+    }
+
+    // the game settings asked for: a preset, or Custom as soon as the range or attempts are set by hand.
+    // the config's range is taken as a whole, and only when no range flag was given, so the two are never mixed.
+    pub fn settings(&self) -> Result<Settings, String> {
+        let (difficulty, low, high, attempts) = if self.range_given() {
+            (self.difficulty, self.low, self.high, self.attempts)
+        } else {
+            let config = &self.defaults;
+            (config.difficulty, config.min.map(i128::from), config.max.map(i128::from), config.attempts)
+        };
+
+        let customised = low.is_some() || high.is_some() || attempts.is_some();
+        let difficulty = match difficulty {
+            Some(difficulty) if customised && difficulty != Difficulty::Custom && self.range_given() => {
+                return Err(format!(
+                    "--min, --max and --attempts cannot be combined with --difficulty {}",
+                    difficulty
+                ))
+            }
+            Some(difficulty) if customised && difficulty != Difficulty::Custom => {
+                return Err(format!("min, max and attempts cannot be combined with difficulty = \"{}\"", difficulty))
+            }
+            Some(difficulty) => difficulty,
+            None if customised => Difficulty::Custom,
+            None => Difficulty::Normal,
+        };
+
+        let mut settings = difficulty.settings();
+        if let Some(low) = low {
+            settings.low = low;
+        }
+        if let Some(high) = high {
+            settings.high = high;
+        }
+        if let Some(attempts) = attempts {
+            settings.max_attempts = if attempts == 0 { None } else { Some(attempts) };
+        }
+
+        settings.temperature = self.temperature;
+
+        settings.validate()?;
+        Ok(settings)
+    }
+
+    // the bulls and cows settings: a preset, or Custom once the digits or attempts are set by hand
+    pub fn bulls_settings(&self) -> Result<BullsSettings, String> {
+        let customised = self.digits.is_some() || self.attempts.is_some();
+        let difficulty = match self.difficulty {
+            Some(difficulty) if customised && difficulty != Difficulty::Custom => {
+                return Err(format!(
+                    "--digits and --attempts cannot be combined with --difficulty {}",
+                    difficulty
+                ))
+            }
+            Some(difficulty) => difficulty,
+            None if customised => Difficulty::Custom,
+            None => Difficulty::Normal,
+        };
+
+        let mut settings = BullsSettings::preset(difficulty);
+        if let Some(digits) = self.digits {
+            settings.digits = digits;
+        }
+        if let Some(attempts) = self.attempts {
+            settings.max_attempts = if attempts == 0 { None } else { Some(attempts) };
+        }
+
+        settings.validate()?;
+        Ok(settings)
+    }
+
+    // decimal mode has no presets: the range defaults to 0..=10 and the attempts follow the range
+    pub fn decimal_settings(&self) -> Result<DecimalSettings, String> {
+        if self.difficulty.is_some() {
+            return Err(String::from("decimal mode has no --difficulty; set --min, --max and --places instead"));
+        }
+
+        let mut settings = DecimalSettings::default();
+        if let Some(low) = self.low {
+            settings.low = low;
+        }
+        if let Some(high) = self.high {
+            settings.high = high;
+        }
+        if let Some(places) = self.places {
+            settings.places = places;
+        }
+        settings.max_attempts = match self.attempts {
+            Some(0) => None,
+            Some(attempts) => Some(attempts),
+            None => Some(settings.default_attempts()),
+        };
+
+        settings.validate()?;
+        Ok(settings)
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid value for {}: {}", flag, value))
+}
+
+fn parse_seed(value: &str) -> Result<u64, String> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid seed: {}", value))
+}
+
+// the value of a flag, either from "--flag=value" or from the next argument
+fn flag_value<I: Iterator<Item = String>>(
+    flag: &str,
+    inline: Option<String>,
+    args: &mut I,
+) -> Result<String, String> {
+    match inline {
+        Some(value) => Ok(value),
+        None => args.next().ok_or_else(|| format!("{} needs a value", flag)),
+    }
+}
@@ -0,0 +1,96 @@
+use std::fmt;
+use std::str::FromStr;
+use crate::locale::Locale;
+
+// commands a player can type instead of a guess, always starting with ':'
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Quit,
+    Hint,
+    History,
+    GiveUp,
+    // write the game to disk and leave, to be resumed later
+    Save,
+    Help,
+}
+
+impl Command {
+    pub const ALL: [Command; 6] = [
+        Command::Hint,
+        Command::History,
+        Command::GiveUp,
+        Command::Save,
+        Command::Quit,
+        Command::Help,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Command::Quit => ":quit",
+            Command::Hint => ":hint",
+            Command::History => ":history",
+            Command::GiveUp => ":giveup",
+            Command::Save => ":save",
+            Command::Help => ":help",
+        }
+    }
+
+    pub fn description(self, locale: Locale) -> &'static str {
+        match self {
+            Command::Quit => locale.text("command_quit"),
+            Command::Hint => locale.text("command_hint"),
+            Command::History => locale.text("command_history"),
+            Command::GiveUp => locale.text("command_giveup"),
+            Command::Save => locale.text("command_save"),
+            Command::Help => locale.text("command_help"),
+        }
+    }
+}
+
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+// a line starting with ':' that isn't one of the commands
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownCommand(pub String);
+
+impl UnknownCommand {
+    pub fn message(&self, locale: Locale) -> String {
+        locale.format("unknown_command", &[("command", &self.0)])
+    }
+}
+
+impl fmt::Display for UnknownCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message(Locale::En))
+    }
+}
+
+impl FromStr for Command {
+    type Err = UnknownCommand;
+
+    fn from_str(s: &str) -> Result<Command, UnknownCommand> {
+        match s.trim().to_lowercase().as_str() {
+            ":quit" | ":q" => Ok(Command::Quit),
+            ":hint" => Ok(Command::Hint),
+            ":history" => Ok(Command::History),
+            ":giveup" => Ok(Command::GiveUp),
+            ":save" => Ok(Command::Save),
+            ":help" | ":h" | ":?" => Ok(Command::Help),
+            other => Err(UnknownCommand(other.to_string())),
+        }
+    }
+}
+
+// Some if the line is meant as a command, even an unknown one; None if it should be read as a guess
+pub fn parse_command(line: &str) -> Option<Result<Command, UnknownCommand>> {
+    let line = line.trim();
+    if line.starts_with(':') {
+        Some(line.parse())
+    } else {
+        None
+    }
+}
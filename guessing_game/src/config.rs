@@ -0,0 +1,257 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use serde::Deserialize;
+use crate::cli::{Command, Options};
+use crate::difficulty::Difficulty;
+use crate::locale::Locale;
+use crate::storage;
+
+// when to colour Too small!, Too big! and You win!
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorChoice {
+    // only when printing to a terminal
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    pub fn enabled(self) -> bool {
+        match self {
+            ColorChoice::Auto => io::stdout().is_terminal(),
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ColorChoice::Auto => "auto",
+            ColorChoice::Always => "always",
+            ColorChoice::Never => "never",
+        }
+    }
+}
+
+impl fmt::Display for ColorChoice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for ColorChoice {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<ColorChoice, String> {
+        match s.trim().to_lowercase().as_str() {
+            "auto" => Ok(ColorChoice::Auto),
+            "always" => Ok(ColorChoice::Always),
+            "never" => Ok(ColorChoice::Never),
+            _ => Err(format!("unknown colour setting: {} (expected auto, always or never)", s)),
+        }
+    }
+}
+
+// what config.toml may hold. every key is optional, and unknown keys are refused so typos show up.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub difficulty: Option<Difficulty>,
+    // TOML integers are 64-bit, so the range is too
+    pub min: Option<i64>,
+    pub max: Option<i64>,
+    // 0 means unlimited, as with --attempts
+    pub attempts: Option<u32>,
+    pub lang: Option<String>,
+    pub name: Option<String>,
+    pub color: Option<ColorChoice>,
+}
+
+// where a setting's value came from, from strongest to weakest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    Flag(&'static str),
+    File,
+    Env(&'static str),
+    Default,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Source::Flag(flag) => f.write_str(flag),
+            Source::File => f.write_str("config file"),
+            Source::Env(name) => write!(f, "${}", name),
+            Source::Default => f.write_str("default"),
+        }
+    }
+}
+
+// $XDG_CONFIG_HOME/guessing_game/config.toml, or ~/.config/guessing_game/config.toml
+pub fn default_path() -> PathBuf {
+    let dir = match (env::var_os("XDG_CONFIG_HOME"), env::var_os("HOME")) {
+        (Some(dir), _) => PathBuf::from(dir),
+        (None, Some(home)) => PathBuf::from(home).join(".config"),
+        (None, None) => PathBuf::from("."),
+    };
+    dir.join("guessing_game").join("config.toml")
+}
+
+impl Config {
+    // a missing file is an empty config
+    pub fn load(path: &Path) -> io::Result<Config> {
+        match fs::read_to_string(path) {
+            Ok(contents) => {
+                let config: Config = toml::from_str(&contents)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.message().to_string()))?;
+                config
+                    .validate()
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                Ok(config)
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if let Some(lang) = &self.lang {
+            lang.parse::<Locale>()?;
+        }
+        if self.name.as_ref().is_some_and(|name| name.trim().is_empty()) {
+            return Err(String::from("name can't be empty"));
+        }
+        let options = Options {
+            defaults: self.clone(),
+            ..Options::default()
+        };
+        options.settings().map(|_| ())
+    }
+}
+
+// whether a command takes anything from the config. the rest don't read it at all, so a broken
+// config can't get in the way of looking at the leaderboard or running a server.
+pub fn applies_to(command: Command) -> bool {
+    !matches!(
+        command,
+        Command::Leaderboard | Command::Stats | Command::Replay | Command::Serve | Command::Api
+    )
+}
+
+// reads the file --config names, which has to exist, or else the default one if there is one
+pub fn load(options: &Options) -> Result<(Config, PathBuf), String> {
+    let (path, required) = match &options.config {
+        Some(path) => (PathBuf::from(path), true),
+        None => (default_path(), false),
+    };
+    if required && !path.is_file() {
+        return Err(format!("no config file at {}", path.display()));
+    }
+    match Config::load(&path) {
+        Ok(config) => Ok((config, path)),
+        Err(e) => Err(format!("could not read {}: {}", path.display(), e)),
+    }
+}
+
+// the config fills in whatever the flags left out. flags always win, and Options::settings falls
+// back on the config's range. the config is specific to this game, so it also wins over LANG and NO_COLOR.
+pub fn apply(options: &mut Options, config: &Config) -> Result<(), String> {
+    if options.player.is_none() {
+        options.player = config.name.clone();
+    }
+    if options.lang.is_none() {
+        options.lang = config.lang.as_deref().map(str::parse).transpose()?;
+    }
+    if options.color.is_none() {
+        options.color = config.color;
+    }
+    options.defaults = config.clone();
+    Ok(())
+}
+
+// whether to colour, once flags, the config and $NO_COLOR have had their say
+pub fn color(options: &Options) -> bool {
+    match options.color {
+        Some(choice) => choice.enabled(),
+        None if env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty()) => false,
+        None => ColorChoice::Auto.enabled(),
+    }
+}
+
+// prints each setting's effective value and where it came from, for `config show`.
+// options are the parsed flags, before apply has merged the config into them.
+pub fn show<W: Write>(mut output: W, options: &Options, config: &Config, path: &Path) -> io::Result<()> {
+    let found = if path.is_file() { "" } else { " (not found)" };
+    writeln!(output, "Config file: {}{}", path.display(), found)?;
+    writeln!(output, "Flags win over the config file, which wins over the environment.")?;
+    writeln!(output)?;
+
+    let mut rows: Vec<(&str, String, Source)> = Vec::new();
+
+    // the range as a group, the way Options::settings will see it
+    let merged = Options {
+        defaults: config.clone(),
+        ..options.clone()
+    };
+    let settings = merged.settings().map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let from = |flag: bool, file: bool, name: &'static str| {
+        if options.range_given() {
+            if flag { Source::Flag(name) } else { Source::Default }
+        } else if file {
+            Source::File
+        } else {
+            Source::Default
+        }
+    };
+    rows.push((
+        "difficulty",
+        settings.difficulty.to_string(),
+        from(options.difficulty.is_some(), config.difficulty.is_some(), "--difficulty"),
+    ));
+    rows.push(("min", settings.low.to_string(), from(options.low.is_some(), config.min.is_some(), "--min")));
+    rows.push(("max", settings.high.to_string(), from(options.high.is_some(), config.max.is_some(), "--max")));
+    rows.push((
+        "attempts",
+        settings.max_attempts.map_or(String::from("unlimited"), |max| max.to_string()),
+        from(options.attempts.is_some(), config.attempts.is_some(), "--attempts"),
+    ));
+
+    let lang = match (options.lang, &config.lang, Locale::from_env_var()) {
+        (Some(lang), _, _) => (lang, Source::Flag("--lang")),
+        (None, Some(lang), _) => (lang.parse().unwrap_or_default(), Source::File),
+        (None, None, Some((name, lang))) => (lang, Source::Env(name)),
+        (None, None, None) => (Locale::default(), Source::Default),
+    };
+    rows.push(("lang", lang.0.to_string(), lang.1));
+
+    let name = match (&options.player, &config.name) {
+        (Some(name), _) => (name.clone(), Source::Flag("--name")),
+        (None, Some(name)) => (name.clone(), Source::File),
+        (None, None) if env::var("USER").is_ok_and(|user| !user.trim().is_empty()) => {
+            (storage::default_player(), Source::Env("USER"))
+        }
+        (None, None) => (storage::default_player(), Source::Default),
+    };
+    rows.push(("name", name.0, name.1));
+
+    let color = match (options.color, config.color) {
+        (Some(choice), _) => (choice.to_string(), Source::Flag("--color")),
+        (None, Some(choice)) => (choice.to_string(), Source::File),
+        (None, None) if env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty()) => {
+            (String::from("never"), Source::Env("NO_COLOR"))
+        }
+        (None, None) => (String::from("auto"), Source::Default),
+    };
+    rows.push(("color", color.0, color.1));
+
+    for (key, value, source) in rows {
+        writeln!(output, "{:<11} {:<12} {}", key, value, source)?;
+    }
+    Ok(())
+}
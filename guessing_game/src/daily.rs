@@ -0,0 +1,98 @@
+use std::cmp::Ordering;
+use std::io;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use crate::difficulty::Settings;
+use crate::game::{Game, Status};
+use crate::storage;
+
+// the puzzle for the current UTC day, so the whole team shares it whatever their timezone
+pub fn today() -> String {
+    storage::format_date(storage::now())
+}
+
+// FNV-1a over the date, so every build agrees on the day's seed
+pub fn seed(date: &str) -> u64 {
+    storage::fnv1a(&[b"guessing_game daily ", date.as_bytes()])
+}
+
+// everyone plays the normal preset, or the same seed would give different secrets
+pub fn settings() -> Settings {
+    Settings::default()
+}
+
+// the day's secret, taken straight from the hash so that it can't change with how a rand
+// release turns seeds into numbers
+pub fn secret(date: &str, settings: &Settings) -> i128 {
+    settings.low + (seed(date) as u128 % settings.size()) as i128
+}
+
+// a spoiler-free summary to paste in chat: one arrow per guess pointing towards the secret
+pub fn share(date: &str, game: &Game) -> String {
+    let score = match game.status() {
+        Status::Won => game.attempts().to_string(),
+        Status::Lost => String::from("X"),
+        _ => String::from("-"),
+    };
+    let limit = match game.settings().max_attempts {
+        Some(max) => max.to_string(),
+        None => String::from("∞"),
+    };
+    let arrows: String = game
+        .history()
+        .iter()
+        .map(|(_, ordering)| match ordering {
+            Ordering::Less => "⬆️",
+            Ordering::Greater => "⬇️",
+            Ordering::Equal => "🎯",
+        })
+        .collect();
+
+    format!("Guess the number daily {}: {}/{}\n{}", date, score, limit, arrows)
+}
+
+// one player's go at one day's puzzle
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Played {
+    pub player: String,
+    pub date: String,
+    // None until the game ends, and forever if it was interrupted
+    pub share: Option<String>,
+}
+
+// who has played which day, so each player only gets one go
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DailyLog {
+    played: Vec<Played>,
+}
+
+impl DailyLog {
+    pub fn default_path() -> PathBuf {
+        storage::data_dir().join("daily.json")
+    }
+
+    // a missing file means nobody has played yet
+    pub fn load(path: &Path) -> io::Result<DailyLog> {
+        storage::load(path)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        storage::save(path, self)
+    }
+
+    pub fn find(&self, player: &str, date: &str) -> Option<&Played> {
+        self.played.iter().find(|p| p.player == player && p.date == date)
+    }
+
+    // adds the player's go, or fills in its result
+    pub fn record(&mut self, player: &str, date: &str, share: Option<String>) {
+        match self.played.iter_mut().find(|p| p.player == player && p.date == date) {
+            Some(played) => played.share = share,
+            None => self.played.push(Played {
+                player: player.to_string(),
+                date: date.to_string(),
+                share,
+            }),
+        }
+    }
+}
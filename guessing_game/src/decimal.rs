@@ -0,0 +1,294 @@
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::time::Instant;
+use rand::Rng;
+use crate::command;
+use crate::difficulty::{Difficulty, Settings};
+use crate::game::{optimum, Game, Outcome, Status};
+use crate::locale::Locale;
+use crate::{run_command, Commands, Summary};
+
+// beyond this the scaled numbers stop fitting comfortably in an i128
+pub const MAX_PLACES: u32 = 18;
+
+// a decimal game: whole-number bounds, and a secret with `places` digits after the point
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecimalSettings {
+    pub low: i128,
+    pub high: i128,
+    pub places: u32,
+    pub max_attempts: Option<u32>,
+}
+
+impl Default for DecimalSettings {
+    // two places between 0 and 10, with the same three spare attempts as the normal game
+    fn default() -> DecimalSettings {
+        let mut settings = DecimalSettings {
+            low: 0,
+            high: 10,
+            places: 2,
+            max_attempts: None,
+        };
+        settings.max_attempts = Some(settings.default_attempts());
+        settings
+    }
+}
+
+impl DecimalSettings {
+    // binary search's worst case over the whole range, plus three
+    pub fn default_attempts(&self) -> u32 {
+        match self.scaled() {
+            Ok(settings) => optimum(settings.size()) + 3,
+            Err(_) => 10,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.places > MAX_PLACES {
+            return Err(format!("--places can be at most {}", MAX_PLACES));
+        }
+        self.scaled()?.validate()
+    }
+
+    // the same game in whole steps of 10^-places, which is how it is played
+    pub fn scaled(&self) -> Result<Settings, String> {
+        let scale = scale(self.places.min(MAX_PLACES));
+        match (self.low.checked_mul(scale), self.high.checked_mul(scale)) {
+            (Some(low), Some(high)) => Ok(Settings::new(Difficulty::Custom, low, high, self.max_attempts)),
+            _ => Err(format!("the range is too wide for {} decimal places", self.places)),
+        }
+    }
+}
+
+fn scale(places: u32) -> i128 {
+    10i128.pow(places)
+}
+
+// writes a scaled number back out with exactly `places` digits after the point
+pub fn format_decimal(value: i128, places: u32) -> String {
+    if places == 0 {
+        return value.to_string();
+    }
+    let scale = scale(places).unsigned_abs();
+    let sign = if value < 0 { "-" } else { "" };
+    let abs = value.unsigned_abs();
+    format!("{}{}.{:0width$}", sign, abs / scale, abs % scale, width = places as usize)
+}
+
+// why a line could not be used as a decimal guess
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvalidDecimal {
+    Empty,
+    NotANumber(String),
+    NaN,
+    Infinite,
+    Overflow,
+    // the bounds are already formatted to the game's precision
+    OutOfRange { guess: String, low: String, high: String },
+}
+
+impl InvalidDecimal {
+    pub fn message(&self, locale: Locale) -> String {
+        match self {
+            InvalidDecimal::Empty => locale.text("invalid_empty").to_string(),
+            InvalidDecimal::NotANumber(text) => locale.format("invalid_not_a_number", &[("text", text)]),
+            InvalidDecimal::NaN => locale.text("decimal_nan").to_string(),
+            InvalidDecimal::Infinite => locale.text("decimal_infinite").to_string(),
+            InvalidDecimal::Overflow => locale.text("invalid_overflow").to_string(),
+            InvalidDecimal::OutOfRange { guess, low, high } => {
+                locale.format("invalid_out_of_range", &[("guess", guess), ("low", low), ("high", high)])
+            }
+        }
+    }
+}
+
+impl fmt::Display for InvalidDecimal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message(Locale::En))
+    }
+}
+
+// a plain decimal such as "3", "-0.5", ".25" or "+7.125", scaled by 10^places.
+// digits past the precision are rounded half away from zero, so a guess within half a step of the
+// secret counts as equal to it; the bool says whether any rounding happened.
+fn parse_plain(text: &str, places: u32) -> Option<Result<(i128, bool), InvalidDecimal>> {
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text.strip_prefix('+').unwrap_or(text)),
+    };
+    let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    let all_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    if whole.is_empty() && fraction.is_empty() || !all_digits(whole) || !all_digits(fraction) {
+        return None;
+    }
+
+    let kept = fraction.len().min(places as usize);
+    let padded = format!("{}{}{}", whole, &fraction[..kept], "0".repeat(places as usize - kept));
+    let rest = &fraction[kept..];
+    let round_up = rest.bytes().next().is_some_and(|b| b >= b'5');
+    let rounded = rest.bytes().any(|b| b != b'0');
+
+    let value = padded
+        .trim_start_matches('0')
+        .bytes()
+        .try_fold(0i128, |value, b| value.checked_mul(10)?.checked_add((b - b'0') as i128))
+        .and_then(|value| value.checked_add(round_up as i128));
+    Some(match value {
+        Some(value) if negative => Ok((-value, rounded)),
+        Some(value) => Ok((value, rounded)),
+        None => Err(InvalidDecimal::Overflow),
+    })
+}
+
+// reads a decimal guess, scaled by 10^places, and checks it against the scaled range.
+// f64 parsing is only used to recognise NaN, infinity and exponents like 2.5e-1; plain decimals
+// are read digit by digit so no precision is lost on the way.
+pub fn parse_guess(line: &str, settings: &DecimalSettings) -> Result<(i128, bool), InvalidDecimal> {
+    let text = line.trim();
+    if text.is_empty() {
+        return Err(InvalidDecimal::Empty);
+    }
+
+    let (guess, rounded) = match parse_plain(text, settings.places) {
+        Some(parsed) => parsed?,
+        None => match text.parse::<f64>() {
+            Ok(value) if value.is_nan() => return Err(InvalidDecimal::NaN),
+            // "1e999" overflows to infinity too, but only "inf" and "infinity" were meant as one
+            Ok(value) if value.is_infinite() && text.to_lowercase().contains("inf") => {
+                return Err(InvalidDecimal::Infinite)
+            }
+            Ok(value) if value.is_infinite() => return Err(InvalidDecimal::Overflow),
+            // Display never uses an exponent, so this goes back through the exact path
+            Ok(value) => parse_plain(&value.to_string(), settings.places)
+                .unwrap_or_else(|| Err(InvalidDecimal::NotANumber(text.to_string())))?,
+            Err(_) => return Err(InvalidDecimal::NotANumber(text.to_string())),
+        },
+    };
+
+    let scaled = settings.scaled().map_err(|_| InvalidDecimal::Overflow)?;
+    if !scaled.contains(guess) {
+        return Err(InvalidDecimal::OutOfRange {
+            guess: format_decimal(guess, settings.places),
+            low: format_decimal(scaled.low, settings.places),
+            high: format_decimal(scaled.high, settings.places),
+        });
+    }
+    Ok((guess, rounded))
+}
+
+// a decimal game in progress, for the commands: the game in whole steps, and how to write them out
+struct Decimal<'a> {
+    game: &'a mut Game,
+    places: u32,
+}
+
+impl Commands for Decimal<'_> {
+    fn quit(&mut self) {
+        self.game.quit();
+    }
+
+    fn give_up(&mut self, locale: Locale) -> String {
+        self.game.give_up();
+        let secret = format_decimal(self.game.secret(), self.places);
+        locale.format("gave_up", &[("attempts", &self.game.attempts()), ("secret", &secret)])
+    }
+
+    fn hint(&mut self, locale: Locale) -> String {
+        let (low, high) = self.game.remaining();
+        let show = |value| format_decimal(value, self.places);
+        locale.format("hint", &[("low", &show(low)), ("high", &show(high))])
+    }
+
+    fn history(&self, locale: Locale) -> Vec<String> {
+        self.game
+            .history()
+            .iter()
+            .enumerate()
+            .map(|(i, (guess, ordering))| {
+                format!("{:>3}. {:<10} {}", i + 1, format_decimal(*guess, self.places), locale.describe(*ordering))
+            })
+            .collect()
+    }
+}
+
+// the number game with a decimal secret. it runs on Game in whole steps of 10^-places, and every
+// number is turned back into a decimal before the player sees it.
+pub fn decimal_game<R: BufRead, W: Write, G: Rng>(
+    mut input: R,
+    mut output: W,
+    settings: DecimalSettings,
+    rng: &mut G,
+    locale: Locale,
+) -> io::Result<Summary> {
+    let scaled = settings.scaled().map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let mut game = Game::new(scaled, rng);
+    let started = Instant::now();
+    let show = |value: i128| format_decimal(value, settings.places);
+
+    writeln!(output, "{}", locale.text("decimal_title"))?;
+    writeln!(
+        output,
+        "{}",
+        locale.format(
+            if settings.places == 1 { "decimal_range_one_place" } else { "decimal_range" },
+            &[("low", &show(scaled.low)), ("high", &show(scaled.high)), ("places", &settings.places)]
+        )
+    )?;
+    if let Some(max) = settings.max_attempts {
+        writeln!(output, "{}", locale.format("you_have_attempts", &[("attempts", &max)]))?;
+    }
+
+    while !game.is_finished() {
+        writeln!(output, "{}", locale.text("prompt"))?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            game.quit();
+            writeln!(output, "{}", locale.format("no_more_input", &[("secret", &show(game.secret()))]))?;
+            break;
+        }
+
+        if let Some(command) = command::parse_command(&line) {
+            let mut decimal = Decimal {
+                game: &mut game,
+                places: settings.places,
+            };
+            match command {
+                Ok(command) => run_command(&mut output, &mut decimal, command, locale)?,
+                Err(unknown) => writeln!(output, "{}", unknown.message(locale))?,
+            }
+            continue;
+        }
+
+        let (guess, rounded) = match parse_guess(&line, &settings) {
+            Ok(parsed) => parsed,
+            Err(invalid) => {
+                writeln!(output, "{}", invalid.message(locale))?;
+                continue;
+            }
+        };
+
+        if rounded {
+            writeln!(
+                output,
+                "{}",
+                locale.format("decimal_rounded", &[("guess", &show(guess)), ("text", &line.trim())])
+            )?;
+        } else {
+            writeln!(output, "{}", locale.format("you_guessed", &[("guess", &show(guess))]))?;
+        }
+        if let Outcome::Guess(ordering) = game.submit(guess) {
+            writeln!(output, "{}", locale.describe(ordering))?;
+        }
+        if game.status() == Status::Lost {
+            writeln!(output, "{}", locale.format("out_of_attempts", &[("secret", &show(game.secret()))]))?;
+        }
+    }
+
+    Ok(Summary {
+        status: game.status(),
+        attempts: game.attempts(),
+        secret: game.secret(),
+        elapsed: started.elapsed(),
+    })
+}
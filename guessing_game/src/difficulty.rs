@@ -0,0 +1,110 @@
+use std::fmt;
+use std::str::FromStr;
+use serde::{Deserialize, Serialize};
+
+// the presets a player can choose from; Custom is any range or limit set by hand
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+    Custom,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::Easy,
+        Difficulty::Normal,
+        Difficulty::Hard,
+        Difficulty::Custom,
+    ];
+
+    pub fn settings(self) -> Settings {
+        match self {
+            Difficulty::Easy => Settings::new(self, 1, 50, Some(10)),
+            Difficulty::Normal | Difficulty::Custom => Settings::new(self, 1, 100, Some(10)),
+            Difficulty::Hard => Settings::new(self, 1, 1000, Some(10)),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+            Difficulty::Custom => "custom",
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Difficulty, String> {
+        match s.trim().to_lowercase().as_str() {
+            "easy" => Ok(Difficulty::Easy),
+            "normal" => Ok(Difficulty::Normal),
+            "hard" => Ok(Difficulty::Hard),
+            "custom" => Ok(Difficulty::Custom),
+            _ => Err(format!("unknown difficulty: {}", s)),
+        }
+    }
+}
+
+// the secret is picked from low..=high.
+// i128 holds every u64 and every negative bound a player could ask for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Settings {
+    pub difficulty: Difficulty,
+    pub low: i128,
+    pub high: i128,
+    // None means the player can keep guessing forever
+    pub max_attempts: Option<u32>,
+    // also say how warm each guess is and whether it was warmer than the last
+    pub temperature: bool,
+}
+
+impl Settings {
+    pub fn new(difficulty: Difficulty, low: i128, high: i128, max_attempts: Option<u32>) -> Settings {
+        Settings {
+            difficulty,
+            low,
+            high,
+            max_attempts,
+            temperature: false,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.low > self.high {
+            return Err(format!("invalid range: {} is greater than {}", self.low, self.high));
+        }
+        if self.max_attempts == Some(0) {
+            return Err(String::from("the attempt limit must be at least 1"));
+        }
+        Ok(())
+    }
+
+    pub fn contains(&self, number: i128) -> bool {
+        self.low <= number && number <= self.high
+    }
+
+    // how many numbers the range holds. the whole i128 range is one more than a u128 can count,
+    // so it comes out one short.
+    pub fn size(&self) -> u128 {
+        self.high.abs_diff(self.low).saturating_add(1)
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Settings {
+        Difficulty::Normal.settings()
+    }
+}
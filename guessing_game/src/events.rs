@@ -0,0 +1,342 @@
+use std::cmp::Ordering;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use serde::{Deserialize, Serialize};
+use crate::difficulty::Settings;
+use crate::game::Game;
+use crate::{describe, storage};
+
+// replays don't wait longer than this between two events, however long the player sat thinking
+pub const MAX_PAUSE: Duration = Duration::from_secs(3);
+
+// how many sessions are kept in the data directory; older ones are removed as new ones start
+pub const KEEP: usize = 20;
+
+// what happened
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Kind {
+    Start,
+    Guess,
+    Result,
+    // a line that was neither a guess nor a command
+    Invalid,
+    Command,
+    Win,
+    Lose,
+    Quit,
+}
+
+// how a guess compared with the secret
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    TooSmall,
+    TooBig,
+    Correct,
+}
+
+impl From<Ordering> for Verdict {
+    fn from(ordering: Ordering) -> Verdict {
+        match ordering {
+            Ordering::Less => Verdict::TooSmall,
+            Ordering::Greater => Verdict::TooBig,
+            Ordering::Equal => Verdict::Correct,
+        }
+    }
+}
+
+impl Verdict {
+    fn ordering(self) -> Ordering {
+        match self {
+            Verdict::TooSmall => Ordering::Less,
+            Verdict::TooBig => Ordering::Greater,
+            Verdict::Correct => Ordering::Equal,
+        }
+    }
+}
+
+// one line of the log.
+// it's a flat struct rather than a tagged enum because serde can't read i128s back through one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Event {
+    // milliseconds since the unix epoch
+    pub time_ms: u64,
+    pub event: Kind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub settings: Option<Settings>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret: Option<i128>,
+    // guesses made before a resume
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub earlier: Vec<i128>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guess: Option<i128>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Verdict>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attempts: Option<u32>,
+    // what the player typed, for commands and invalid lines
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input: Option<String>,
+    // why an invalid line was refused, or how a game was left
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+impl Event {
+    fn new(event: Kind) -> Event {
+        Event {
+            time_ms: now_ms(),
+            event,
+            settings: None,
+            seed: None,
+            secret: None,
+            earlier: Vec::new(),
+            guess: None,
+            result: None,
+            attempts: None,
+            input: None,
+            reason: None,
+        }
+    }
+
+    pub fn start(game: &Game, seed: u64) -> Event {
+        Event {
+            settings: Some(*game.settings()),
+            seed: Some(seed),
+            secret: Some(game.secret()),
+            earlier: game.history().iter().map(|(guess, _)| *guess).collect(),
+            ..Event::new(Kind::Start)
+        }
+    }
+
+    pub fn guess(guess: i128) -> Event {
+        Event {
+            guess: Some(guess),
+            ..Event::new(Kind::Guess)
+        }
+    }
+
+    pub fn result(ordering: Ordering) -> Event {
+        Event {
+            result: Some(ordering.into()),
+            ..Event::new(Kind::Result)
+        }
+    }
+
+    pub fn invalid(input: &str, reason: String) -> Event {
+        Event {
+            input: Some(input.trim().to_string()),
+            reason: Some(reason),
+            ..Event::new(Kind::Invalid)
+        }
+    }
+
+    pub fn command(input: &str) -> Event {
+        Event {
+            input: Some(input.trim().to_string()),
+            ..Event::new(Kind::Command)
+        }
+    }
+
+    pub fn win(attempts: u32) -> Event {
+        Event {
+            attempts: Some(attempts),
+            ..Event::new(Kind::Win)
+        }
+    }
+
+    pub fn lose(game: &Game) -> Event {
+        Event {
+            attempts: Some(game.attempts()),
+            secret: Some(game.secret()),
+            ..Event::new(Kind::Lose)
+        }
+    }
+
+    // reason is one of "quit", "gave_up", "saved" or "end_of_input"
+    pub fn quit(game: &Game, reason: &str) -> Event {
+        Event {
+            attempts: Some(game.attempts()),
+            secret: Some(game.secret()),
+            reason: Some(reason.to_string()),
+            ..Event::new(Kind::Quit)
+        }
+    }
+}
+
+// a JSON Lines file that grows one event at a time
+#[derive(Debug)]
+pub struct EventLog {
+    file: File,
+    // set once a write fails; the rest of the game goes unlogged
+    stopped: bool,
+}
+
+impl EventLog {
+    // sessions/<unix seconds>-<seed>.jsonl under the data directory
+    pub fn default_path(seed: u64) -> PathBuf {
+        sessions_dir().join(format!("{}-{}.jsonl", storage::now(), seed))
+    }
+
+    pub fn create(path: &Path) -> io::Result<EventLog> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(EventLog { file, stopped: false })
+    }
+
+    // each event is written straight through, so a killed game still leaves its log behind
+    pub fn write(&mut self, event: &Event) -> io::Result<()> {
+        let line = serde_json::to_string(event)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        writeln!(self.file, "{}", line)
+    }
+
+    // like write, but a failure only warns and turns the log off: a full disk shouldn't end a game
+    pub fn record(&mut self, event: &Event) {
+        if self.stopped {
+            return;
+        }
+        if let Err(e) = self.write(event) {
+            eprintln!("guessing_game: could not write the event log, so the rest of this game won't be logged: {}", e);
+            self.stopped = true;
+        }
+    }
+}
+
+fn sessions_dir() -> PathBuf {
+    storage::data_dir().join("sessions")
+}
+
+// every logged session, oldest first, going by the timestamps in the file names
+fn sessions() -> io::Result<Vec<PathBuf>> {
+    let entries = match fs::read_dir(sessions_dir()) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+
+    let mut sessions: Vec<(u64, PathBuf)> = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        let started = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.split('-').next())
+            .and_then(|secs| secs.parse().ok());
+        if let Some(started) = started {
+            sessions.push((started, path));
+        }
+    }
+    sessions.sort();
+    Ok(sessions.into_iter().map(|(_, path)| path).collect())
+}
+
+// the most recently started session
+pub fn latest() -> io::Result<Option<PathBuf>> {
+    Ok(sessions()?.pop())
+}
+
+// removes all but the newest `keep` sessions
+pub fn prune(keep: usize) -> io::Result<()> {
+    let sessions = sessions()?;
+    let old = sessions.len().saturating_sub(keep);
+    for path in &sessions[..old] {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+pub fn load(path: &Path) -> io::Result<Vec<Event>> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut events = Vec::new();
+    for (number, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let event = serde_json::from_str(&line).map_err(|e| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("line {}: {}", number + 1, e))
+        })?;
+        events.push(event);
+    }
+    Ok(events)
+}
+
+// prints a logged session the way it played out. speed 2.0 is twice as fast; 0 doesn't wait at all.
+pub fn replay<W: Write>(mut output: W, events: &[Event], speed: f64) -> io::Result<()> {
+    let first = match events.first() {
+        Some(event) => event.time_ms,
+        None => return writeln!(output, "The log is empty."),
+    };
+    let mut previous = first;
+
+    for event in events {
+        if speed > 0.0 {
+            let gap = Duration::from_millis(event.time_ms.saturating_sub(previous));
+            thread::sleep(gap.div_f64(speed).min(MAX_PAUSE));
+        }
+        previous = event.time_ms;
+
+        let at = Duration::from_millis(event.time_ms.saturating_sub(first)).as_secs_f64();
+        let secret = event.secret.map_or(String::from("?"), |s| s.to_string());
+        match event.event {
+            Kind::Start => {
+                writeln!(output, "Game started {}.", storage::format_date(event.time_ms / 1000))?;
+                if let Some(settings) = &event.settings {
+                    write!(output, "The secret was {}, between {} and {}", secret, settings.low, settings.high)?;
+                    match settings.max_attempts {
+                        Some(max) => writeln!(output, ", with {} attempts.", max)?,
+                        None => writeln!(output, ".")?,
+                    }
+                }
+                if let Some(seed) = event.seed {
+                    writeln!(output, "Seed: {}", seed)?;
+                }
+                if !event.earlier.is_empty() {
+                    let earlier: Vec<String> = event.earlier.iter().map(|g| g.to_string()).collect();
+                    writeln!(output, "Resumed after guessing {}.", earlier.join(", "))?;
+                }
+            }
+            Kind::Guess => {
+                if let Some(guess) = event.guess {
+                    writeln!(output, "[{:>6.1}s] You guessed: {}", at, guess)?;
+                }
+            }
+            Kind::Result => {
+                if let Some(result) = event.result {
+                    writeln!(output, "          {}", describe(result.ordering()))?;
+                }
+            }
+            Kind::Invalid => writeln!(output, "[{:>6.1}s] {}", at, event.reason.as_deref().unwrap_or("invalid"))?,
+            Kind::Command => writeln!(output, "[{:>6.1}s] {}", at, event.input.as_deref().unwrap_or(""))?,
+            Kind::Win => writeln!(output, "Won in {} attempts after {:.1}s.", event.attempts.unwrap_or(0), at)?,
+            Kind::Lose => writeln!(output, "Out of attempts after {:.1}s. The secret number was {}.", at, secret)?,
+            Kind::Quit => writeln!(
+                output,
+                "Left after {} attempts and {:.1}s ({}). The secret number was {}.",
+                event.attempts.unwrap_or(0),
+                at,
+                event.reason.as_deref().unwrap_or("quit").replace('_', " "),
+                secret
+            )?,
+        }
+        output.flush()?;
+    }
+    Ok(())
+}
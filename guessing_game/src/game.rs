@@ -0,0 +1,146 @@
+use std::cmp::Ordering;
+use rand::Rng;
+use crate::difficulty::Settings;
+
+// the result of submitting a guess to a Game
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    // how the guess compared to the secret: Less is "Too small!", Greater is "Too big!"
+    Guess(Ordering),
+    // the game was already over, so the guess was not counted
+    Finished,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Playing,
+    Won,
+    // every allowed attempt was used without finding the secret
+    Lost,
+    // the player asked for the secret, ending the game without a win
+    GaveUp,
+    // the player left before the game was decided
+    Quit,
+}
+
+impl Status {
+    // the process exit code for a finished game, so scripts can tell a win from a loss or a quit
+    pub fn exit_code(self) -> i32 {
+        match self {
+            Status::Won => 0,
+            Status::Lost | Status::GaveUp => 1,
+            Status::Playing | Status::Quit => 3,
+        }
+    }
+}
+
+// how many guesses binary search needs, at worst, to find any number in a range this big
+pub fn optimum(size: u128) -> u32 {
+    128 - size.max(1).leading_zeros()
+}
+
+// a single round of guess the number, with no knowledge of stdin or stdout
+#[derive(Debug, Clone)]
+pub struct Game {
+    settings: Settings,
+    secret: i128,
+    attempts: u32,
+    status: Status,
+    // every counted guess with how it compared to the secret
+    history: Vec<(i128, Ordering)>,
+    // the narrowest interval consistent with the answers given so far
+    low: i128,
+    high: i128,
+}
+
+impl Game {
+    // picks the secret from the settings' range using any random number generator
+    pub fn new<G: Rng>(settings: Settings, rng: &mut G) -> Game {
+        let secret = rng.gen_range(settings.low..=settings.high);
+        Game::with_secret(settings, secret)
+    }
+
+    pub fn with_secret(settings: Settings, secret: i128) -> Game {
+        Game {
+            settings,
+            secret,
+            attempts: 0,
+            status: Status::Playing,
+            history: Vec::new(),
+            low: settings.low,
+            high: settings.high,
+        }
+    }
+
+    pub fn submit(&mut self, guess: i128) -> Outcome {
+        if self.is_finished() {
+            return Outcome::Finished;
+        }
+
+        self.attempts += 1;
+        let ordering = guess.cmp(&self.secret);
+        self.history.push((guess, ordering));
+
+        match ordering {
+            Ordering::Less => self.low = self.low.max(guess + 1),
+            Ordering::Greater => self.high = self.high.min(guess - 1),
+            Ordering::Equal => {}
+        }
+
+        if ordering == Ordering::Equal {
+            self.status = Status::Won;
+        } else if self.attempts_left() == Some(0) {
+            self.status = Status::Lost;
+        }
+
+        Outcome::Guess(ordering)
+    }
+
+    // ends an undecided game without a winner
+    pub fn quit(&mut self) {
+        if !self.is_finished() {
+            self.status = Status::Quit;
+        }
+    }
+
+    pub fn give_up(&mut self) {
+        if !self.is_finished() {
+            self.status = Status::GaveUp;
+        }
+    }
+
+    pub fn history(&self) -> &[(i128, Ordering)] {
+        &self.history
+    }
+
+    // the interval the secret must be in, given every "Too small!" and "Too big!" so far
+    pub fn remaining(&self) -> (i128, i128) {
+        (self.low, self.high)
+    }
+
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    pub fn secret(&self) -> i128 {
+        self.secret
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    pub fn attempts_left(&self) -> Option<u32> {
+        self.settings
+            .max_attempts
+            .map(|max| max.saturating_sub(self.attempts))
+    }
+
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.status != Status::Playing
+    }
+}
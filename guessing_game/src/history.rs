@@ -0,0 +1,117 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+use crate::difficulty::{Difficulty, Settings};
+use crate::game::{Game, Status};
+use crate::storage;
+use crate::strategy::{self, BinarySearch};
+
+// how a finished game ended. games that were quit or saved aren't finished, so they aren't here.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Ending {
+    Won,
+    Lost,
+    GaveUp,
+}
+
+// one finished number game
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameRecord {
+    pub player: String,
+    // seconds since the unix epoch
+    pub timestamp: u64,
+    pub difficulty: Difficulty,
+    pub low: i128,
+    pub high: i128,
+    pub ending: Ending,
+    pub secret: i128,
+    pub guesses: Vec<i128>,
+    // how many guesses binary search needs for the same secret
+    pub optimal: u32,
+    pub duration_ms: u64,
+}
+
+impl GameRecord {
+    // None for a game that isn't over, or was left without finishing
+    pub fn from_game(player: &str, game: &Game, duration_ms: u64) -> Option<GameRecord> {
+        let ending = match game.status() {
+            Status::Won => Ending::Won,
+            Status::Lost => Ending::Lost,
+            Status::GaveUp => Ending::GaveUp,
+            Status::Playing | Status::Quit => return None,
+        };
+        let settings = game.settings();
+
+        Some(GameRecord {
+            player: player.to_string(),
+            timestamp: storage::now(),
+            difficulty: settings.difficulty,
+            low: settings.low,
+            high: settings.high,
+            ending,
+            secret: game.secret(),
+            guesses: game.history().iter().map(|(guess, _)| *guess).collect(),
+            optimal: optimal_guesses(settings, game.secret()),
+            duration_ms,
+        })
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.guesses.len() as u32
+    }
+}
+
+// plays the secret with binary search, with no attempt limit
+pub fn optimal_guesses(settings: &Settings, secret: i128) -> u32 {
+    let settings = Settings {
+        max_attempts: None,
+        ..*settings
+    };
+    let mut game = Game::with_secret(settings, secret);
+    // binary search never draws from the rng, so any seed does
+    strategy::play(&mut BinarySearch, &mut game, &mut StdRng::seed_from_u64(0));
+    game.attempts()
+}
+
+// every finished game, kept as a JSON file next to the leaderboard
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct History {
+    games: Vec<GameRecord>,
+}
+
+impl History {
+    pub fn default_path() -> PathBuf {
+        storage::data_dir().join("history.json")
+    }
+
+    // a missing file is an empty history
+    pub fn load(path: &Path) -> io::Result<History> {
+        storage::load(path)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        storage::save(path, self)
+    }
+
+    pub fn record(&mut self, game: GameRecord) {
+        self.games.push(game);
+    }
+
+    // everyone who has finished a game, in alphabetical order
+    pub fn players(&self) -> Vec<&str> {
+        let mut players: Vec<&str> = self.games.iter().map(|game| game.player.as_str()).collect();
+        players.sort_unstable();
+        players.dedup();
+        players
+    }
+
+    // a player's games, oldest first
+    pub fn games_of(&self, player: &str) -> Vec<&GameRecord> {
+        let mut games: Vec<&GameRecord> = self.games.iter().filter(|game| game.player == player).collect();
+        games.sort_by_key(|game| game.timestamp);
+        games
+    }
+}
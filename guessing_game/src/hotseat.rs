@@ -0,0 +1,227 @@
+use std::io::{self, BufRead, Write};
+use rand::Rng;
+use crate::command::{self, Command};
+use crate::difficulty::Settings;
+use crate::game::{Game, Outcome, Status};
+use crate::locale::Locale;
+use crate::{input, run_command};
+
+pub const MIN_PLAYERS: usize = 2;
+pub const MAX_PLAYERS: usize = 8;
+
+// one player's totals over a match
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standing {
+    pub name: String,
+    // rounds won, ties included
+    pub score: u32,
+    // guesses over every round
+    pub attempts: u32,
+}
+
+pub fn validate(players: &[String], rounds: u32) -> Result<(), String> {
+    if players.len() < MIN_PLAYERS || players.len() > MAX_PLAYERS {
+        return Err(format!(
+            "hot seat needs between {} and {} players, got {}",
+            MIN_PLAYERS,
+            MAX_PLAYERS,
+            players.len()
+        ));
+    }
+    if rounds == 0 {
+        return Err(String::from("a match needs at least one round"));
+    }
+    Ok(())
+}
+
+// how a player's turn ended
+enum Turn {
+    Guessed,
+    GaveUp,
+    // :quit or the end of input stops the whole match
+    Quit,
+}
+
+// reads lines until the player makes a valid guess, gives up or quits
+fn take_turn<R: BufRead, W: Write>(
+    input: &mut R,
+    output: &mut W,
+    name: &str,
+    game: &mut Game,
+    locale: Locale,
+) -> io::Result<Turn> {
+    loop {
+        let attempt = game.attempts() + 1;
+        writeln!(output, "{}", locale.format("hotseat_prompt", &[("name", &name), ("attempt", &attempt)]))?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(Turn::Quit);
+        }
+
+        if let Some(command) = command::parse_command(&line) {
+            match command {
+                Ok(Command::Quit) => return Ok(Turn::Quit),
+                // the others may share the secret, so giving up keeps it hidden until the round ends
+                Ok(Command::GiveUp) => {
+                    game.give_up();
+                    writeln!(output, "{}", locale.format("hotseat_gives_up", &[("name", &name)]))?;
+                    return Ok(Turn::GaveUp);
+                }
+                Ok(command) => run_command(output, game, command, locale)?,
+                Err(unknown) => writeln!(output, "{}", unknown.message(locale))?,
+            }
+            continue;
+        }
+
+        let guess = match input::parse_guess(&line, game.settings()) {
+            Ok(num) => num,
+            Err(invalid) => {
+                writeln!(output, "{}", invalid.message(locale))?;
+                continue;
+            }
+        };
+
+        if let Outcome::Guess(ordering) = game.submit(guess) {
+            let reply = locale.describe(ordering);
+            writeln!(
+                output,
+                "{}",
+                locale.format("hotseat_guessed", &[("name", &name), ("guess", &guess), ("reply", &reply)])
+            )?;
+            if game.status() == Status::Lost {
+                writeln!(output, "{}", locale.format("hotseat_out_of_attempts", &[("name", &name)]))?;
+            }
+        }
+        return Ok(Turn::Guessed);
+    }
+}
+
+fn names(standings: &[&Standing], locale: Locale) -> String {
+    let names: Vec<&str> = standings.iter().map(|s| s.name.as_str()).collect();
+    names.join(&format!(" {} ", locale.text("and")))
+}
+
+// plays a best-of-`rounds` match with everyone taking turns at one terminal.
+// each round every player gets the same number of turns, so two players can find the secret on
+// the same lap and tie. returns the final standings, or None if someone quit.
+#[allow(clippy::too_many_arguments)]
+pub fn hot_seat<R: BufRead, W: Write, G: Rng>(
+    mut input: R,
+    mut output: W,
+    settings: Settings,
+    players: &[String],
+    shared: bool,
+    rounds: u32,
+    rng: &mut G,
+    locale: Locale,
+) -> io::Result<Option<Vec<Standing>>> {
+    let mut standings: Vec<Standing> = players
+        .iter()
+        .map(|name| Standing {
+            name: name.clone(),
+            score: 0,
+            attempts: 0,
+        })
+        .collect();
+
+    writeln!(output, "{}", locale.format("hotseat_title", &[("players", &players.join(", "))]))?;
+    writeln!(
+        output,
+        "{}",
+        locale.format(
+            if shared { "hotseat_shared" } else { "hotseat_own" },
+            &[("rounds", &rounds), ("low", &settings.low), ("high", &settings.high)]
+        )
+    )?;
+
+    for round in 1..=rounds {
+        writeln!(output)?;
+        writeln!(output, "{}", locale.format("hotseat_round", &[("round", &round)]))?;
+
+        let mut games: Vec<Game> = if shared {
+            vec![Game::new(settings, rng); players.len()]
+        } else {
+            players.iter().map(|_| Game::new(settings, rng)).collect()
+        };
+
+        while games.iter().all(|game| game.status() != Status::Won)
+            && games.iter().any(|game| !game.is_finished())
+        {
+            for (i, game) in games.iter_mut().enumerate() {
+                if game.is_finished() {
+                    continue;
+                }
+                if let Turn::Quit = take_turn(&mut input, &mut output, &players[i], game, locale)? {
+                    writeln!(output, "{}", locale.text("hotseat_abandoned"))?;
+                    return Ok(None);
+                }
+            }
+        }
+
+        for (standing, game) in standings.iter_mut().zip(&games) {
+            standing.attempts += game.attempts();
+            if game.status() == Status::Won {
+                standing.score += 1;
+            }
+        }
+
+        let winners: Vec<&Standing> = standings
+            .iter()
+            .zip(&games)
+            .filter(|(_, game)| game.status() == Status::Won)
+            .map(|(standing, _)| standing)
+            .collect();
+        let result = match winners.len() {
+            0 => locale.text("hotseat_nobody").to_string(),
+            1 => locale.format("hotseat_round_winner", &[("name", &winners[0].name), ("round", &round)]),
+            _ => locale.format("hotseat_round_tie", &[("round", &round), ("names", &names(&winners, locale))]),
+        };
+        writeln!(output, "{}", result)?;
+
+        if shared {
+            writeln!(output, "{}", locale.format("hotseat_secret", &[("secret", &games[0].secret())]))?;
+        } else {
+            for (name, game) in players.iter().zip(&games) {
+                let secret = game.secret();
+                writeln!(output, "{}", locale.format("hotseat_own_secret", &[("name", name), ("secret", &secret)]))?;
+            }
+        }
+
+        writeln!(output, "{}", locale.format("hotseat_score", &[("scores", &score_line(&standings))]))?;
+
+        // stop early once nobody can catch the leader in the rounds that are left
+        let mut scores: Vec<u32> = standings.iter().map(|s| s.score).collect();
+        scores.sort_unstable_by(|a, b| b.cmp(a));
+        if round < rounds && scores[0] > scores[1] + (rounds - round) {
+            break;
+        }
+    }
+
+    writeln!(output)?;
+    let best = standings.iter().map(|s| s.score).max().unwrap_or(0);
+    let leaders: Vec<&Standing> = standings.iter().filter(|s| s.score == best).collect();
+    if leaders.len() == 1 {
+        writeln!(output, "{}", locale.format("hotseat_match_winner", &[("name", &leaders[0].name)]))?;
+    } else {
+        writeln!(output, "{}", locale.format("hotseat_match_tie", &[("names", &names(&leaders, locale))]))?;
+    }
+    for standing in &standings {
+        writeln!(
+            output,
+            "  {:<16} {}",
+            standing.name,
+            locale.format("hotseat_standing", &[("wins", &standing.score), ("guesses", &standing.attempts)])
+        )?;
+    }
+
+    Ok(Some(standings))
+}
+
+fn score_line(standings: &[Standing]) -> String {
+    let scores: Vec<String> = standings
+        .iter()
+        .map(|s| format!("{} {}", s.name, s.score))
+        .collect();
+    scores.join(", ")
+}
@@ -0,0 +1,62 @@
+use std::fmt;
+use std::num::IntErrorKind;
+use crate::difficulty::Settings;
+use crate::locale::Locale;
+
+// why a line could not be used as a guess
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvalidGuess {
+    Empty,
+    NotANumber(String),
+    // a negative number when the range only holds positive numbers
+    Negative(i128),
+    // a number too large (or too small) to be parsed at all
+    Overflow,
+    OutOfRange { guess: i128, low: i128, high: i128 },
+}
+
+impl InvalidGuess {
+    pub fn message(&self, locale: Locale) -> String {
+        match self {
+            InvalidGuess::Empty => locale.text("invalid_empty").to_string(),
+            InvalidGuess::NotANumber(text) => locale.format("invalid_not_a_number", &[("text", text)]),
+            InvalidGuess::Negative(guess) => locale.format("invalid_negative", &[("guess", guess)]),
+            InvalidGuess::Overflow => locale.text("invalid_overflow").to_string(),
+            InvalidGuess::OutOfRange { guess, low, high } => locale.format(
+                "invalid_out_of_range",
+                &[("guess", guess), ("low", low), ("high", high)],
+            ),
+        }
+    }
+}
+
+impl fmt::Display for InvalidGuess {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message(Locale::En))
+    }
+}
+
+// reads a guess from a line of input and checks it against the settings' range.
+// the ParseIntError kind tells apart empty input, stray characters and overflow.
+pub fn parse_guess(line: &str, settings: &Settings) -> Result<i128, InvalidGuess> {
+    let text = line.trim();
+
+    let guess: i128 = text.parse().map_err(|e: std::num::ParseIntError| match e.kind() {
+        IntErrorKind::Empty => InvalidGuess::Empty,
+        IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => InvalidGuess::Overflow,
+        _ => InvalidGuess::NotANumber(text.to_string()),
+    })?;
+
+    if guess < 0 && settings.low >= 0 {
+        return Err(InvalidGuess::Negative(guess));
+    }
+    if !settings.contains(guess) {
+        return Err(InvalidGuess::OutOfRange {
+            guess,
+            low: settings.low,
+            high: settings.high,
+        });
+    }
+
+    Ok(guess)
+}
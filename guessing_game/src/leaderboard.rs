@@ -0,0 +1,122 @@
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use crate::difficulty::Difficulty;
+use crate::storage;
+
+// how many entries are shown for each difficulty
+pub const TOP: usize = 10;
+
+// which game a win was in, since their attempt counts can't be compared
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Variant {
+    #[default]
+    Number,
+    BullsAndCows,
+}
+
+impl Variant {
+    pub const ALL: [Variant; 2] = [Variant::Number, Variant::BullsAndCows];
+}
+
+// one won game
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    pub player: String,
+    // files written before bulls and cows existed only hold number games
+    #[serde(default)]
+    pub variant: Variant,
+    pub difficulty: Difficulty,
+    pub low: i128,
+    pub high: i128,
+    pub attempts: u32,
+    pub duration_ms: u64,
+    // seconds since the unix epoch
+    pub timestamp: u64,
+}
+
+impl Entry {
+    pub fn duration(&self) -> Duration {
+        Duration::from_millis(self.duration_ms)
+    }
+
+    pub fn date(&self) -> String {
+        storage::format_date(self.timestamp)
+    }
+}
+
+// every recorded win, kept as a JSON file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Leaderboard {
+    entries: Vec<Entry>,
+}
+
+impl Leaderboard {
+    pub fn default_path() -> PathBuf {
+        storage::data_dir().join("leaderboard.json")
+    }
+
+    // a missing file is an empty leaderboard, not an error
+    pub fn load(path: &Path) -> io::Result<Leaderboard> {
+        storage::load(path)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        storage::save(path, self)
+    }
+
+    pub fn record(&mut self, entry: Entry) {
+        self.entries.push(entry);
+    }
+
+    pub fn has_entries(&self, variant: Variant) -> bool {
+        self.entries.iter().any(|entry| entry.variant == variant)
+    }
+
+    // the best entries for a difficulty: fewest attempts first, then the fastest
+    pub fn top(&self, variant: Variant, difficulty: Difficulty, count: usize) -> Vec<&Entry> {
+        let mut entries: Vec<&Entry> = self
+            .entries
+            .iter()
+            .filter(|entry| entry.variant == variant && entry.difficulty == difficulty)
+            .collect();
+        entries.sort_by_key(|entry| (entry.attempts, entry.duration_ms, entry.timestamp));
+        entries.truncate(count);
+        entries
+    }
+
+    pub fn print_top<W: Write>(
+        &self,
+        output: &mut W,
+        variant: Variant,
+        difficulty: Difficulty,
+    ) -> io::Result<()> {
+        let entries = self.top(variant, difficulty, TOP);
+
+        match variant {
+            Variant::Number => writeln!(output, "Top {} ({}):", TOP, difficulty)?,
+            Variant::BullsAndCows => writeln!(output, "Top {} (bulls and cows, {}):", TOP, difficulty)?,
+        }
+        if entries.is_empty() {
+            return writeln!(output, "  no wins yet");
+        }
+
+        for (rank, entry) in entries.iter().enumerate() {
+            writeln!(
+                output,
+                "{:>3}. {:<16} {:>3} attempts  {:>7.1}s  {}..={}  {}",
+                rank + 1,
+                entry.player,
+                entry.attempts,
+                entry.duration().as_secs_f64(),
+                entry.low,
+                entry.high,
+                entry.date()
+            )?;
+        }
+
+        Ok(())
+    }
+}
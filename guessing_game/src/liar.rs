@@ -0,0 +1,497 @@
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+use std::rc::Rc;
+use std::time::Instant;
+use rand::Rng;
+use crate::command;
+use crate::difficulty::Settings;
+use crate::game::Status;
+use crate::locale::Locale;
+use crate::{input, run_command, Commands, Summary};
+
+// the solver keeps one counter per candidate, so the range has to stay reasonably small
+pub const MAX_RANGE: u128 = 1_000_000;
+
+// the sum of C(questions, i) for i in 0..=lies: how many answer sequences a single
+// candidate can produce when up to `lies` of `questions` answers may be false
+pub fn volume(questions: u32, lies: u32) -> u128 {
+    let mut total: u128 = 0;
+    let mut binomial: u128 = 1;
+    for i in 0..=lies.min(questions) {
+        total = total.saturating_add(binomial);
+        binomial = binomial.saturating_mul((questions - i) as u128) / (i as u128 + 1);
+    }
+    total
+}
+
+// a lower bound on the questions any strategy needs to be sure of the secret among n numbers
+// when up to `lies` replies are false. this is Berlekamp's volume argument adapted to guesses:
+// the candidates' total weight sum(volume(q, lies left)) is conserved by every reply except that
+// a wrong guess rules out the guessed number for certain ("You win!" never lies), and a lying
+// oracle can always keep the heavier of "Too small!" and "Too big!".
+pub fn min_questions(n: u128, lies: u32) -> u32 {
+    (0..128)
+        .find(|&q| {
+            let mut weight = n as f64 * volume(q, lies) as f64;
+            for asked in 0..q {
+                weight = ((weight - volume(q - asked, lies) as f64) / 2.0).max(0.0);
+            }
+            weight <= 1.0
+        })
+        .unwrap_or(128)
+}
+
+// checks that liar mode can be played on the settings' range
+pub fn validate(settings: &Settings) -> Result<(), String> {
+    if settings.size() > MAX_RANGE {
+        return Err(format!("liar mode supports ranges of at most {} numbers", MAX_RANGE));
+    }
+    Ok(())
+}
+
+// the number side of Ulam's game: answers honestly except for up to `lies` flipped replies
+#[derive(Debug, Clone)]
+pub struct Oracle {
+    secret: i128,
+    lies_left: u32,
+    // the answer numbers (starting at 1) that were lies
+    lied_on: Vec<u32>,
+    answers: u32,
+}
+
+impl Oracle {
+    pub fn new(secret: i128, lies: u32) -> Oracle {
+        Oracle {
+            secret,
+            lies_left: lies,
+            lied_on: Vec::new(),
+            answers: 0,
+        }
+    }
+
+    // "You win!" is never a lie; "Too small!" and "Too big!" are flipped a third of the time while lies remain
+    pub fn reply<G: Rng + ?Sized>(&mut self, guess: i128, rng: &mut G) -> Ordering {
+        self.answers += 1;
+
+        let truth = guess.cmp(&self.secret);
+        if truth != Ordering::Equal && self.lies_left > 0 && rng.gen_ratio(1, 3) {
+            self.lies_left -= 1;
+            self.lied_on.push(self.answers);
+            return truth.reverse();
+        }
+        truth
+    }
+
+    pub fn secret(&self) -> i128 {
+        self.secret
+    }
+
+    pub fn lied_on(&self) -> &[u32] {
+        &self.lied_on
+    }
+}
+
+// the solver's search grows quickly with the range and the lies, so it is kept to games it can
+// finish in a moment: the most numbers it takes on with no lies, one lie, two and three
+pub const MAX_SOLVER_RANGE: [u128; 4] = [100, 100, 40, 20];
+
+// checks that the solver can play the settings' range with this many lies
+pub fn validate_solver(settings: &Settings, lies: u32) -> Result<(), String> {
+    match MAX_SOLVER_RANGE.get(lies as usize) {
+        None => Err(format!("the solver handles at most {} lies", MAX_SOLVER_RANGE.len() - 1)),
+        Some(&max) if settings.size() > max => Err(format!(
+            "with {} lies the solver handles ranges of at most {} numbers",
+            lies, max
+        )),
+        Some(_) => Ok(()),
+    }
+}
+
+// the most total weight (see Solver::fits) a state can have and still be settled in `questions`
+fn capacity(questions: u32, lies: u32) -> u128 {
+    (1..=questions).fold(1u128, |capacity, q| capacity.saturating_mul(2).saturating_add(volume(q, lies)))
+}
+
+// finds the secret in spite of up to `lies` false answers, with as few questions as any strategy
+// could be sure of. every candidate carries how many answers it would make lies; a candidate is out
+// once that passes the limit.
+//
+// only the order of the candidates still in and their lie counts matter, so a state is that list.
+// guessing a candidate always beats guessing between two of them (the replies leave less behind,
+// and a win ends it), so the search only tries candidates, and a minimax search over those states
+// finds the fewest questions that settle every way the oracle can answer. it is pruned with
+// Berlekamp's volume argument: a candidate with q questions and l lies to go can still produce
+// volume(q, l) answer sequences, a guess splits that total between the two replies except for the
+// guessed number, and a state whose total is over capacity(q) can't be settled in q questions.
+// a searched state's lie counts, with the fewest questions it might take and the fewest it is known to take
+type Known = HashMap<Vec<u8>, (u32, u32)>;
+
+#[derive(Debug, Clone)]
+pub struct Solver {
+    low: i128,
+    lies: u32,
+    // errors[i] is the number of answers that would be lies if the secret were low + i;
+    // None once the guess itself was low + i, since "You win!" never lies
+    errors: Vec<Option<u32>>,
+    questions: u32,
+    // states repeat across guesses, so clones share what was found about them
+    known: Rc<RefCell<Known>>,
+}
+
+impl Solver {
+    pub fn new(settings: &Settings, lies: u32) -> Solver {
+        let size = settings.size().min(MAX_RANGE) as usize;
+        Solver {
+            low: settings.low,
+            lies,
+            errors: vec![Some(0); size],
+            questions: 0,
+            known: Rc::default(),
+        }
+    }
+
+    fn alive(&self, errors: Option<u32>) -> bool {
+        matches!(errors, Some(e) if e <= self.lies)
+    }
+
+    // the candidates still consistent with at most `lies` false answers
+    pub fn candidates(&self) -> Vec<i128> {
+        self.errors
+            .iter()
+            .enumerate()
+            .filter(|(_, &e)| self.alive(e))
+            .map(|(i, _)| self.low + i as i128)
+            .collect()
+    }
+
+    // the secret, once only one candidate is left
+    pub fn identified(&self) -> Option<i128> {
+        let candidates = self.candidates();
+        match candidates.as_slice() {
+            [only] => Some(*only),
+            _ => None,
+        }
+    }
+
+    // how many questions have been asked so far
+    pub fn questions(&self) -> u32 {
+        self.questions
+    }
+
+    // the lie counts of the candidates still in, in order
+    fn state(&self) -> Vec<u8> {
+        self.errors
+            .iter()
+            .filter(|&&e| self.alive(e))
+            .map(|e| e.unwrap_or(0) as u8)
+            .collect()
+    }
+
+    // the states left by "Too small!" and "Too big!" after guessing the candidate at `guess`
+    fn replies(&self, state: &[u8], guess: usize) -> (Vec<u8>, Vec<u8>) {
+        let lied = |e: &u8| (u32::from(*e) < self.lies).then_some(e + 1);
+        let too_small = state[..guess].iter().filter_map(lied).chain(state[guess + 1..].iter().copied()).collect();
+        let too_big = state[..guess].iter().copied().chain(state[guess + 1..].iter().filter_map(lied)).collect();
+        (too_small, too_big)
+    }
+
+    // whether the state's total weight leaves any hope of settling it in `questions`
+    fn fits(&self, state: &[u8], questions: u32) -> bool {
+        let total = state
+            .iter()
+            .fold(0u128, |sum, &e| sum.saturating_add(volume(questions, self.lies - u32::from(e))));
+        total <= capacity(questions, self.lies)
+    }
+
+    // the guesses whose replies both fit in `questions`, the most even split first
+    fn promising(&self, state: &[u8], questions: u32) -> Vec<usize> {
+        let weight = |e: u32| if e <= self.lies { volume(questions, self.lies - e) } else { 0 };
+        // kept[i]: weight of the candidates before i if the reply leaves them alone,
+        // lied[i]: their weight if it makes each of them one lie worse
+        let mut kept = vec![0u128; state.len() + 1];
+        let mut lied = vec![0u128; state.len() + 1];
+        for (i, &e) in state.iter().enumerate() {
+            kept[i + 1] = kept[i].saturating_add(weight(u32::from(e)));
+            lied[i + 1] = lied[i].saturating_add(weight(u32::from(e) + 1));
+        }
+
+        let n = state.len();
+        let capacity = capacity(questions, self.lies);
+        let mut guesses: Vec<(u128, usize)> = (0..n)
+            .filter_map(|guess| {
+                // "Too small!" means the secret is above the guess, so everything below it is one more lie
+                let too_small = lied[guess].saturating_add(kept[n] - kept[guess + 1]);
+                let too_big = kept[guess].saturating_add(lied[n] - lied[guess + 1]);
+                (too_small <= capacity && too_big <= capacity).then_some((too_small.max(too_big), guess))
+            })
+            .collect();
+        guesses.sort_unstable();
+        guesses.into_iter().map(|(_, guess)| guess).collect()
+    }
+
+    // whether every way the oracle can answer leaves one candidate within `questions` questions
+    fn settles(&self, state: &[u8], questions: u32) -> bool {
+        if state.len() <= 1 {
+            return true;
+        }
+        if questions == 0 {
+            return false;
+        }
+        // a state and its mirror image take the same number of questions
+        let key: Vec<u8> = state.iter().rev().copied().collect::<Vec<u8>>().min(state.to_vec());
+        if let Some(&(fewest, known)) = self.known.borrow().get(&key) {
+            if questions >= known {
+                return true;
+            }
+            if questions < fewest {
+                return false;
+            }
+        }
+
+        let settled = self.fits(state, questions)
+            && self.promising(state, questions - 1).into_iter().any(|guess| {
+                let (too_small, too_big) = self.replies(state, guess);
+                self.settles(&too_small, questions - 1) && self.settles(&too_big, questions - 1)
+            });
+
+        let mut known = self.known.borrow_mut();
+        let entry = known.entry(key).or_insert((0, u32::MAX));
+        if settled {
+            entry.1 = entry.1.min(questions);
+        } else {
+            entry.0 = entry.0.max(questions + 1);
+        }
+        settled
+    }
+
+    // the fewest further questions that are sure to identify the secret from here
+    pub fn questions_needed(&self) -> u32 {
+        let state = self.state();
+        (0..).find(|&q| self.settles(&state, q)).unwrap_or(u32::MAX)
+    }
+
+    pub fn next_guess(&self) -> i128 {
+        let alive: Vec<usize> = (0..self.errors.len()).filter(|&i| self.alive(self.errors[i])).collect();
+        let state = self.state();
+        if state.len() <= 1 {
+            return self.low + alive.first().copied().unwrap_or(0) as i128;
+        }
+
+        // a guess whose replies can both be settled in one question fewer than the whole state
+        let remaining = self.questions_needed() - 1;
+        let guess = self
+            .promising(&state, remaining)
+            .into_iter()
+            .find(|&guess| {
+                let (too_small, too_big) = self.replies(&state, guess);
+                self.settles(&too_small, remaining) && self.settles(&too_big, remaining)
+            })
+            .unwrap_or(0);
+        self.low + alive[guess] as i128
+    }
+
+    // updates every candidate's lie count from the reply to a guess
+    pub fn record(&mut self, guess: i128, reply: Ordering) {
+        self.questions += 1;
+
+        let Ok(index) = usize::try_from(guess - self.low) else {
+            return;
+        };
+        for (i, errors) in self.errors.iter_mut().enumerate() {
+            if errors.is_none() {
+                continue;
+            }
+            // reply is how the guess compared to the secret, so Less ("Too small!") makes
+            // every candidate below the guess one more lie
+            match reply {
+                Ordering::Equal if i != index => *errors = None,
+                Ordering::Equal => {}
+                _ if i == index => *errors = None,
+                Ordering::Less if i < index => *errors = errors.map(|e| e + 1),
+                Ordering::Greater if i > index => *errors = errors.map(|e| e + 1),
+                _ => {}
+            }
+        }
+    }
+}
+
+fn lies_line(oracle: &Oracle, locale: Locale) -> String {
+    match oracle.lied_on() {
+        [] => locale.text("liar_never_lied").to_string(),
+        lied_on => {
+            let answers: Vec<String> = lied_on.iter().map(|a| format!("#{}", a)).collect();
+            locale.format("liar_lied_on", &[("answers", &answers.join(", "))])
+        }
+    }
+}
+
+// a game of liar mode in progress
+struct Round {
+    oracle: Oracle,
+    replies: Vec<(i128, Ordering)>,
+    status: Status,
+}
+
+impl Commands for Round {
+    fn quit(&mut self) {
+        self.status = Status::Quit;
+    }
+
+    fn give_up(&mut self, locale: Locale) -> String {
+        self.status = Status::GaveUp;
+        let lies = lies_line(&self.oracle, locale);
+        locale.format("liar_gave_up", &[("secret", &self.oracle.secret()), ("lies", &lies)])
+    }
+
+    fn hint(&mut self, locale: Locale) -> String {
+        locale.text("liar_no_hints").to_string()
+    }
+
+    fn history(&self, locale: Locale) -> Vec<String> {
+        self.replies
+            .iter()
+            .enumerate()
+            .map(|(i, (guess, reply))| format!("{:>3}. {:<10} {}", i + 1, guess, locale.describe(*reply)))
+            .collect()
+    }
+}
+
+// the human side of liar mode: guess the number while the replies may lie up to `lies` times
+pub fn liar_game<R: BufRead, W: Write, G: Rng>(
+    mut input: R,
+    mut output: W,
+    settings: Settings,
+    lies: u32,
+    rng: &mut G,
+    locale: Locale,
+) -> io::Result<Summary> {
+    let mut round = Round {
+        oracle: Oracle::new(rng.gen_range(settings.low..=settings.high), lies),
+        replies: Vec::new(),
+        status: Status::Playing,
+    };
+    let started = Instant::now();
+
+    // each possible lie costs the questions it takes to correct it, on top of the usual limit
+    let size = settings.size();
+    let extra = min_questions(size, lies) - min_questions(size, 0);
+    let max_attempts = settings.max_attempts.map(|max| max.saturating_add(extra));
+
+    writeln!(output, "{}", locale.text("liar_title"))?;
+    writeln!(
+        output,
+        "{}",
+        locale.format("liar_range", &[("low", &settings.low), ("high", &settings.high), ("lies", &lies)])
+    )?;
+    if let Some(max) = max_attempts {
+        writeln!(output, "{}", locale.format("you_have_attempts", &[("attempts", &max)]))?;
+    }
+
+    while round.status == Status::Playing {
+        writeln!(output, "{}", locale.text("prompt"))?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            round.status = Status::Quit;
+            writeln!(output, "{}", locale.format("no_more_input", &[("secret", &round.oracle.secret())]))?;
+            break;
+        }
+
+        if let Some(command) = command::parse_command(&line) {
+            match command {
+                Ok(command) => run_command(&mut output, &mut round, command, locale)?,
+                Err(unknown) => writeln!(output, "{}", unknown.message(locale))?,
+            }
+            continue;
+        }
+
+        let guess = match input::parse_guess(&line, &settings) {
+            Ok(num) => num,
+            Err(invalid) => {
+                writeln!(output, "{}", invalid.message(locale))?;
+                continue;
+            }
+        };
+
+        writeln!(output, "{}", locale.format("you_guessed", &[("guess", &guess)]))?;
+        let reply = round.oracle.reply(guess, rng);
+        round.replies.push((guess, reply));
+        writeln!(output, "{}", locale.describe(reply))?;
+
+        if reply == Ordering::Equal {
+            round.status = Status::Won;
+            writeln!(output, "{}", lies_line(&round.oracle, locale))?;
+        } else if max_attempts.is_some_and(|max| round.replies.len() as u32 >= max) {
+            round.status = Status::Lost;
+            let lies = lies_line(&round.oracle, locale);
+            writeln!(
+                output,
+                "{}",
+                locale.format("liar_out_of_attempts", &[("secret", &round.oracle.secret()), ("lies", &lies)])
+            )?;
+        }
+    }
+
+    Ok(Summary {
+        status: round.status,
+        attempts: round.replies.len() as u32,
+        secret: round.oracle.secret(),
+        elapsed: started.elapsed(),
+    })
+}
+
+// the solver bot against a lying oracle, narrating each question.
+// returns how many questions it took to identify the secret.
+pub fn solve_game<W: Write, G: Rng>(
+    mut output: W,
+    settings: Settings,
+    lies: u32,
+    rng: &mut G,
+    locale: Locale,
+) -> io::Result<u32> {
+    let mut oracle = Oracle::new(rng.gen_range(settings.low..=settings.high), lies);
+    let mut solver = Solver::new(&settings, lies);
+
+    writeln!(
+        output,
+        "{}",
+        locale.format("liar_solver_start", &[("low", &settings.low), ("high", &settings.high), ("lies", &lies)])
+    )?;
+    writeln!(
+        output,
+        "{}",
+        locale.format("liar_solver_bound", &[("questions", &solver.questions_needed())])
+    )?;
+
+    let secret = loop {
+        if let Some(secret) = solver.identified() {
+            break secret;
+        }
+
+        let guess = solver.next_guess();
+        let reply = oracle.reply(guess, rng);
+        let reply_text = locale.describe(reply);
+        writeln!(
+            output,
+            "{}",
+            locale.format("liar_solver_question", &[("guess", &guess), ("reply", &reply_text)])
+        )?;
+        solver.record(guess, reply);
+
+        if reply == Ordering::Equal {
+            break guess;
+        }
+    };
+
+    writeln!(
+        output,
+        "{}",
+        locale.format(
+            "liar_solver_found",
+            &[("secret", &secret), ("questions", &solver.questions()), ("lies", &lies_line(&oracle, locale))]
+        )
+    )?;
+    Ok(solver.questions())
+}
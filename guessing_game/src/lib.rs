@@ -0,0 +1,321 @@
+use std::cmp::Ordering;
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use rand::Rng;
+
+pub mod api;
+pub mod bulls;
+pub mod cli;
+pub mod clock;
+pub mod command;
+pub mod config;
+pub mod daily;
+pub mod decimal;
+pub mod difficulty;
+pub mod events;
+pub mod game;
+pub mod history;
+pub mod hotseat;
+pub mod input;
+pub mod leaderboard;
+pub mod liar;
+pub mod locale;
+pub mod multiplayer;
+pub mod rating;
+pub mod reverse;
+pub mod save;
+pub mod simulate;
+pub mod stats;
+pub mod storage;
+pub mod strategy;
+pub mod temperature;
+pub mod timeattack;
+#[cfg(feature = "tui")]
+pub mod tui;
+
+pub use difficulty::{Difficulty, Settings};
+pub use game::{Game, Outcome, Status};
+pub use leaderboard::Leaderboard;
+
+use command::Command;
+use events::{Event, EventLog};
+use locale::Locale;
+use save::SavedGame;
+
+// how a finished game went, for the leaderboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Summary {
+    pub status: Status,
+    pub attempts: u32,
+    pub secret: i128,
+    pub elapsed: Duration,
+}
+
+// what a game needs beyond its settings to be saved and resumed
+#[derive(Debug, Clone, Default)]
+pub struct Session {
+    // the seed the secret came from, kept in the save
+    pub seed: u64,
+    // where :save writes the game; None turns saving off
+    pub save_path: Option<PathBuf>,
+    // time already played before a resume
+    pub elapsed: Duration,
+    // refreshed after every guess so a Ctrl-C handler can save the latest state
+    pub checkpoint: Option<Arc<Mutex<Option<SavedGame>>>>,
+    // where every start, guess, result and ending is written down
+    pub log: Option<Arc<Mutex<EventLog>>>,
+    // the language the game talks in
+    pub locale: Locale,
+    // colour Too small!, Too big! and You win!
+    pub color: bool,
+}
+
+impl Session {
+    fn update_checkpoint(&self, game: &Game, elapsed: Duration) {
+        if let Some(checkpoint) = &self.checkpoint {
+            let saved = if game.is_finished() {
+                None
+            } else {
+                Some(SavedGame::from_game(game, self.seed, elapsed))
+            };
+            *checkpoint.lock().unwrap() = saved;
+        }
+    }
+
+    // the log is a nice-to-have, so failing to write it never stops the game
+    pub(crate) fn log(&self, event: Event) {
+        if let Some(log) = &self.log {
+            log.lock().unwrap().record(&event);
+        }
+    }
+
+    // the last event of a game; reason says how it was left if it wasn't won or lost
+    pub(crate) fn log_end(&self, game: &Game, reason: &str) {
+        match game.status() {
+            Status::Won => self.log(Event::win(game.attempts())),
+            Status::Lost => self.log(Event::lose(game)),
+            Status::GaveUp => self.log(Event::quit(game, "gave_up")),
+            Status::Quit | Status::Playing => self.log(Event::quit(game, reason)),
+        }
+    }
+}
+
+// plays one game reading guesses from input and writing prompts to output.
+// taking the reader, writer and rng as parameters lets a test drive a round without a terminal.
+pub fn guess_the_number<R: BufRead, W: Write, G: Rng>(
+    input: R,
+    output: W,
+    settings: Settings,
+    rng: &mut G,
+) -> io::Result<Summary> {
+    play_game(input, output, &mut Game::new(settings, rng), &Session::default())
+}
+
+// plays a game that may already have guesses in it, as after a resume.
+// the game is borrowed so the caller can still look at its history afterwards.
+pub fn play_game<R: BufRead, W: Write>(
+    mut input: R,
+    mut output: W,
+    game: &mut Game,
+    session: &Session,
+) -> io::Result<Summary> {
+    let locale = session.locale;
+    writeln!(output, "{}", locale.text("title"))?;
+
+    let settings = *game.settings();
+    let started = Instant::now();
+    let elapsed = |started: Instant| session.elapsed + started.elapsed();
+
+    match settings.max_attempts {
+        Some(max) => writeln!(
+            output,
+            "{}",
+            locale.format(
+                "range_attempts",
+                &[("low", &settings.low), ("high", &settings.high), ("attempts", &max)]
+            )
+        )?,
+        None => writeln!(
+            output,
+            "{}",
+            locale.format("range", &[("low", &settings.low), ("high", &settings.high)])
+        )?,
+    }
+    writeln!(output, "{}", locale.text("help_hint"))?;
+
+    if game.attempts() > 0 {
+        writeln!(output, "{}", locale.format("welcome_back", &[("attempts", &game.attempts())]))?;
+    }
+    session.update_checkpoint(game, elapsed(started));
+    session.log(Event::start(game, session.seed));
+    let mut reason = "quit";
+
+    while !game.is_finished() {
+        writeln!(output, "{}", locale.text("prompt"))?;
+
+        let mut line = String::new();
+
+        // read_line returns Ok(0) once stdin is closed, so treat that as the player leaving
+        if input.read_line(&mut line)? == 0 {
+            game.quit();
+            reason = "end_of_input";
+            writeln!(output, "{}", locale.format("no_more_input", &[("secret", &game.secret())]))?;
+            break;
+        }
+
+        if let Some(command) = command::parse_command(&line) {
+            session.log(Event::command(&line));
+            match command {
+                Ok(Command::Save) => match &session.save_path {
+                    Some(path) => {
+                        SavedGame::from_game(game, session.seed, elapsed(started)).save(path)?;
+                        game.quit();
+                        reason = "saved";
+                        writeln!(output, "{}", locale.text("saved"))?;
+                    }
+                    None => writeln!(output, "{}", locale.text("save_unavailable"))?,
+                },
+                Ok(command) => run_command(&mut output, game, command, locale)?,
+                Err(unknown) => writeln!(output, "{}", unknown.message(locale))?,
+            }
+            continue;
+        }
+
+        let guess = match input::parse_guess(&line, &settings) {
+            Ok(num) => num,
+            Err(invalid) => {
+                session.log(Event::invalid(&line, invalid.to_string()));
+                writeln!(output, "{}", invalid.message(locale))?;
+                continue;
+            }
+        };
+
+        writeln!(output, "{}", locale.format("you_guessed", &[("guess", &guess)]))?;
+        session.log(Event::guess(guess));
+
+        match game.submit(guess) {
+            Outcome::Guess(ordering) => {
+                session.log(Event::result(ordering));
+                writeln!(output, "{}", paint(locale.describe(ordering), ordering, session.color))?
+            }
+            Outcome::Finished => break,
+        }
+
+        if settings.temperature {
+            if let Some(feedback) = temperature::feedback(game, locale) {
+                writeln!(output, "{}", feedback)?;
+            }
+        }
+
+        if game.status() == Status::Lost {
+            writeln!(output, "{}", locale.format("out_of_attempts", &[("secret", &game.secret())]))?;
+        }
+
+        session.update_checkpoint(game, elapsed(started));
+    }
+
+    session.log_end(game, reason);
+
+    // a saved or abandoned game is no longer anything a Ctrl-C should write out
+    if let Some(checkpoint) = &session.checkpoint {
+        *checkpoint.lock().unwrap() = None;
+    }
+
+    Ok(Summary {
+        status: game.status(),
+        attempts: game.attempts(),
+        secret: game.secret(),
+        elapsed: elapsed(started),
+    })
+}
+
+// the reply in the colours the full-screen view uses: yellow when too small, cyan when too big and
+// bold green for a win
+fn paint(text: &str, ordering: Ordering, color: bool) -> String {
+    if !color {
+        return text.to_string();
+    }
+    let code = match ordering {
+        Ordering::Less => "33",
+        Ordering::Greater => "36",
+        Ordering::Equal => "1;32",
+    };
+    format!("\x1b[{}m{}\x1b[0m", code, text)
+}
+
+// what the player is told after a guess, in English; Locale::describe has the others
+pub fn describe(ordering: Ordering) -> &'static str {
+    Locale::En.describe(ordering)
+}
+
+// what the in-game commands act on. Game is the usual one; modes that keep their own state say how
+// they give up, hint and list the guesses so far.
+pub(crate) trait Commands {
+    fn quit(&mut self);
+    // ends the game and says what the secret was
+    fn give_up(&mut self, locale: Locale) -> String;
+    fn hint(&mut self, locale: Locale) -> String;
+    // one line per guess so far
+    fn history(&self, locale: Locale) -> Vec<String>;
+}
+
+impl Commands for Game {
+    fn quit(&mut self) {
+        Game::quit(self);
+    }
+
+    fn give_up(&mut self, locale: Locale) -> String {
+        Game::give_up(self);
+        locale.format("gave_up", &[("attempts", &self.attempts()), ("secret", &self.secret())])
+    }
+
+    fn hint(&mut self, locale: Locale) -> String {
+        let (low, high) = self.remaining();
+        locale.format("hint", &[("low", &low), ("high", &high)])
+    }
+
+    fn history(&self, locale: Locale) -> Vec<String> {
+        Game::history(self)
+            .iter()
+            .enumerate()
+            .map(|(i, (guess, ordering))| {
+                format!("{:>3}. {:<10} {:?} ({})", i + 1, guess, ordering, locale.describe(*ordering))
+            })
+            .collect()
+    }
+}
+
+pub(crate) fn run_command<W: Write, C: Commands>(
+    output: &mut W,
+    game: &mut C,
+    command: Command,
+    locale: Locale,
+) -> io::Result<()> {
+    match command {
+        Command::Quit => {
+            game.quit();
+            writeln!(output, "{}", locale.text("goodbye"))
+        }
+        Command::GiveUp => writeln!(output, "{}", game.give_up(locale)),
+        Command::Hint => writeln!(output, "{}", game.hint(locale)),
+        Command::History => {
+            let lines = game.history(locale);
+            if lines.is_empty() {
+                return writeln!(output, "{}", locale.text("no_guesses"));
+            }
+            for line in lines {
+                writeln!(output, "{}", line)?;
+            }
+            Ok(())
+        }
+        Command::Help => {
+            for command in Command::ALL {
+                writeln!(output, "  {:<9} {}", command.name(), command.description(locale))?;
+            }
+            Ok(())
+        }
+        Command::Save => writeln!(output, "{}", locale.text("save_unavailable")),
+    }
+}
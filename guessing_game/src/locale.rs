@@ -0,0 +1,469 @@
+use std::cmp::Ordering;
+use std::env;
+use std::fmt;
+use std::str::FromStr;
+
+// the languages the number game speaks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Locale {
+    #[default]
+    En,
+    Es,
+    De,
+}
+
+// every message as (key, text). {name} marks a value filled in by Locale::format.
+pub type Catalog = &'static [(&'static str, &'static str)];
+
+const EN: Catalog = &[
+    ("title", "Guess the number!"),
+    ("range", "I'm thinking of a number between {low} and {high}."),
+    ("range_attempts", "I'm thinking of a number between {low} and {high}. You have {attempts} attempts."),
+    ("help_hint", "Type :help for commands."),
+    ("welcome_back", "Welcome back! You have made {attempts} guesses so far; :history lists them."),
+    ("prompt", "Please input your guess."),
+    ("no_more_input", "No more input. The secret number was {secret}."),
+    ("saved", "Game saved. Pick it up again with --resume."),
+    ("save_unavailable", "Saving is not available in this game."),
+    ("you_guessed", "You guessed: {guess}"),
+    ("too_small", "Too small!"),
+    ("too_big", "Too big!"),
+    ("you_win", "You win!"),
+    ("out_of_attempts", "Out of attempts! The secret number was {secret}."),
+    ("goodbye", "Goodbye!"),
+    ("gave_up", "You gave up after {attempts} attempts. The secret number was {secret}."),
+    ("hint", "Hint: the secret number is between {low} and {high}."),
+    ("no_guesses", "No guesses yet."),
+    ("unknown_command", "Unknown command '{command}'. Type :help for a list."),
+    ("command_quit", "leave without revealing the secret"),
+    ("command_hint", "show the range the secret must be in"),
+    ("command_history", "list your guesses so far"),
+    ("command_giveup", "reveal the secret and end the game"),
+    ("command_save", "save the game and leave; resume with --resume"),
+    ("command_help", "show this list"),
+    ("invalid_empty", "Please type a number."),
+    ("invalid_not_a_number", "'{text}' is not a number."),
+    ("invalid_negative", "{guess} is negative; the secret number is never below zero."),
+    ("invalid_overflow", "That number is far too large to be the secret."),
+    ("invalid_out_of_range", "{guess} is out of range; guess between {low} and {high}."),
+    ("temperature_burning", "Burning!"),
+    ("temperature_hot", "Hot."),
+    ("temperature_warm", "Warm."),
+    ("temperature_cold", "Cold."),
+    ("temperature_freezing", "Freezing."),
+    ("trend_warmer", "Warmer than your last guess."),
+    ("trend_colder", "Colder than your last guess."),
+    ("trend_same", "Just as far as your last guess."),
+    ("reverse_think", "Think of a number between {low} and {high}."),
+    ("reverse_instructions", "Answer each guess with higher, lower or correct (or :quit)."),
+    ("reverse_is_it", "Is it {guess}?"),
+    ("reverse_not_an_answer", "'{text}' is not an answer. Type higher, lower or correct."),
+    ("reverse_conflict_higher", "'{answer}' for {guess} contradicts answer #{number}, where you said your number was higher than {earlier}."),
+    ("reverse_conflict_lower", "'{answer}' for {guess} contradicts answer #{number}, where you said your number was lower than {earlier}."),
+    ("reverse_outside_range", "'{answer}' for {guess} would put your number outside the range you picked from."),
+    ("reverse_got_it", "Got it! Your number is {number}. I needed {guesses} guesses."),
+    ("you_have_attempts", "You have {attempts} attempts."),
+    ("liar_title", "Guess the number! (liar mode)"),
+    ("liar_range", "I'm thinking of a number between {low} and {high}, and I may lie up to {lies} times."),
+    ("liar_never_lied", "I never lied."),
+    ("liar_lied_on", "I lied on answers {answers}."),
+    ("liar_gave_up", "The secret number was {secret}. {lies}"),
+    ("liar_no_hints", "No hints when I might be lying!"),
+    ("liar_out_of_attempts", "Out of attempts! The secret number was {secret}. {lies}"),
+    ("liar_solver_start", "Finding a number between {low} and {high} with up to {lies} lies."),
+    ("liar_solver_bound", "It will be sure of it within {questions} questions, and no strategy can be sure in fewer."),
+    ("liar_solver_question", "Is it {guess}? {reply}"),
+    ("liar_solver_found", "The number is {secret}, found with {questions} questions. {lies}"),
+    ("and", "and"),
+    ("hotseat_title", "Guess the number! Hot seat for {players}."),
+    ("hotseat_shared", "Best of {rounds}. One shared secret between {low} and {high}."),
+    ("hotseat_own", "Best of {rounds}. Everyone has their own secret between {low} and {high}."),
+    ("hotseat_round", "Round {round}"),
+    ("hotseat_prompt", "{name}, your guess (attempt {attempt}):"),
+    ("hotseat_gives_up", "{name} gives up this round."),
+    ("hotseat_guessed", "{name} guessed {guess}: {reply}"),
+    ("hotseat_out_of_attempts", "{name} is out of attempts."),
+    ("hotseat_abandoned", "Match abandoned."),
+    ("hotseat_nobody", "Nobody found it this round."),
+    ("hotseat_round_winner", "{name} wins round {round}!"),
+    ("hotseat_round_tie", "Round {round} is a tie between {names}!"),
+    ("hotseat_secret", "The secret number was {secret}."),
+    ("hotseat_own_secret", "{name}'s secret number was {secret}."),
+    ("hotseat_score", "Score: {scores}"),
+    ("hotseat_match_winner", "{name} wins the match!"),
+    ("hotseat_match_tie", "The match is a tie between {names}."),
+    ("hotseat_standing", "{wins} wins, {guesses} guesses"),
+    ("bulls_title", "Bulls and cows!"),
+    ("bulls_code", "I'm thinking of a code of {digits} different digits."),
+    ("bulls_code_attempts", "I'm thinking of a code of {digits} different digits. You have {attempts} attempts."),
+    ("bulls_no_more_input", "No more input. The code was {code}."),
+    ("bulls_gave_up", "You gave up after {attempts} attempts. The code was {code}."),
+    ("bulls_no_hints", "No hints for codes of more than {digits} digits."),
+    ("bulls_hint", "Hint: {count} codes are still possible. Try {guess}."),
+    ("bulls_out_of_attempts", "Out of attempts! The code was {code}."),
+    ("bull", "bull"),
+    ("bulls", "bulls"),
+    ("cow", "cow"),
+    ("cows", "cows"),
+    ("code_wrong_length", "The code has {expected} digits, not {got}."),
+    ("code_not_a_digit", "'{text}' is not a digit."),
+    ("code_repeated", "{digit} appears more than once; every digit is different."),
+    ("bulls_solver_start", "Cracking a code of {digits} different digits."),
+    ("bulls_solver_found", "The code is {code}, found in {guesses} guesses."),
+    ("time_attack_title", "Time attack! Find as many secrets as you can in {seconds} seconds."),
+    ("time_attack_range", "Every secret is between {low} and {high}."),
+    ("time_attack_secret", "Secret #{number}."),
+    ("time_attack_prompt", "[{seconds}s left] Please input your guess."),
+    ("time_attack_no_more_input", "No more input."),
+    ("time_attack_times_up", "Time's up! The secret number was {secret}."),
+    ("time_attack_score", "Final score: {solved} found, {missed} missed, {guesses} guesses."),
+    ("time_attack_average", "That's {average} guesses per secret found."),
+    ("tui_instructions", "Type a guess and press Enter. :help lists the commands."),
+    ("tui_title", "Guess the number! It is between {low} and {high}."),
+    ("tui_still_possible", " Still possible: {low}..={high} "),
+    ("tui_guesses", " Guesses "),
+    ("tui_guess_column", "Guess"),
+    ("tui_top", " Top {count} ({difficulty}) "),
+    ("tui_no_wins", "no wins yet"),
+    ("tui_attempts_left", "Attempts: {attempts}, {left} left"),
+    ("tui_attempts_no_limit", "Attempts: {attempts}, no limit"),
+    ("tui_press_any_key", "Press any key to leave."),
+    ("tui_your_guess", " Your guess "),
+    ("decimal_title", "Guess the number! (decimal mode)"),
+    ("decimal_range", "I'm thinking of a number between {low} and {high}, to {places} decimal places."),
+    ("decimal_range_one_place", "I'm thinking of a number between {low} and {high}, to 1 decimal place."),
+    ("decimal_rounded", "You guessed: {guess} (rounded from {text})"),
+    ("decimal_nan", "NaN is not a number you can guess."),
+    ("decimal_infinite", "Infinity is never the secret."),
+];
+
+const ES: Catalog = &[
+    ("title", "¡Adivina el número!"),
+    ("range", "Estoy pensando en un número entre {low} y {high}."),
+    ("range_attempts", "Estoy pensando en un número entre {low} y {high}. Tienes {attempts} intentos."),
+    ("help_hint", "Escribe :help para ver los comandos."),
+    ("welcome_back", "¡Bienvenido de nuevo! Llevas {attempts} intentos; :history los muestra."),
+    ("prompt", "Por favor, introduce tu número."),
+    ("no_more_input", "No hay más entrada. El número secreto era {secret}."),
+    ("saved", "Partida guardada. Continúala con --resume."),
+    ("save_unavailable", "En esta partida no se puede guardar."),
+    ("you_guessed", "Has dicho: {guess}"),
+    ("too_small", "¡Demasiado pequeño!"),
+    ("too_big", "¡Demasiado grande!"),
+    ("you_win", "¡Has ganado!"),
+    ("out_of_attempts", "¡Sin intentos! El número secreto era {secret}."),
+    ("goodbye", "¡Adiós!"),
+    ("gave_up", "Te has rendido después de {attempts} intentos. El número secreto era {secret}."),
+    ("hint", "Pista: el número secreto está entre {low} y {high}."),
+    ("no_guesses", "Todavía no hay intentos."),
+    ("unknown_command", "Comando desconocido '{command}'. Escribe :help para ver la lista."),
+    ("command_quit", "salir sin revelar el número secreto"),
+    ("command_hint", "mostrar el rango donde está el número secreto"),
+    ("command_history", "listar tus intentos hasta ahora"),
+    ("command_giveup", "revelar el número secreto y terminar la partida"),
+    ("command_save", "guardar la partida y salir; continúa con --resume"),
+    ("command_help", "mostrar esta lista"),
+    ("invalid_empty", "Por favor, escribe un número."),
+    ("invalid_not_a_number", "'{text}' no es un número."),
+    ("invalid_negative", "{guess} es negativo; el número secreto nunca es menor que cero."),
+    ("invalid_overflow", "Ese número es demasiado grande para ser el secreto."),
+    ("invalid_out_of_range", "{guess} está fuera del rango; elige entre {low} y {high}."),
+    ("temperature_burning", "¡Te quemas!"),
+    ("temperature_hot", "Caliente."),
+    ("temperature_warm", "Templado."),
+    ("temperature_cold", "Frío."),
+    ("temperature_freezing", "Helado."),
+    ("trend_warmer", "Más caliente que tu último intento."),
+    ("trend_colder", "Más frío que tu último intento."),
+    ("trend_same", "Igual de lejos que tu último intento."),
+    ("reverse_think", "Piensa en un número entre {low} y {high}."),
+    ("reverse_instructions", "Responde a cada intento con higher, lower o correct (o :quit)."),
+    ("reverse_is_it", "¿Es {guess}?"),
+    ("reverse_not_an_answer", "'{text}' no es una respuesta. Escribe higher, lower o correct."),
+    ("reverse_conflict_higher", "'{answer}' para {guess} contradice la respuesta #{number}, donde dijiste que tu número era mayor que {earlier}."),
+    ("reverse_conflict_lower", "'{answer}' para {guess} contradice la respuesta #{number}, donde dijiste que tu número era menor que {earlier}."),
+    ("reverse_outside_range", "'{answer}' para {guess} dejaría tu número fuera del rango que elegiste."),
+    ("reverse_got_it", "¡Lo tengo! Tu número es {number}. Necesité {guesses} intentos."),
+    ("you_have_attempts", "Tienes {attempts} intentos."),
+    ("liar_title", "¡Adivina el número! (modo mentiroso)"),
+    ("liar_range", "Estoy pensando en un número entre {low} y {high}, y puedo mentir hasta {lies} veces."),
+    ("liar_never_lied", "Nunca mentí."),
+    ("liar_lied_on", "Mentí en las respuestas {answers}."),
+    ("liar_gave_up", "El número secreto era {secret}. {lies}"),
+    ("liar_no_hints", "¡No hay pistas cuando puedo estar mintiendo!"),
+    ("liar_out_of_attempts", "¡Sin intentos! El número secreto era {secret}. {lies}"),
+    ("liar_solver_start", "Buscando un número entre {low} y {high} con hasta {lies} mentiras."),
+    ("liar_solver_bound", "Estará seguro en {questions} preguntas como mucho, y ninguna estrategia puede estarlo con menos."),
+    ("liar_solver_question", "¿Es {guess}? {reply}"),
+    ("liar_solver_found", "El número es {secret}, encontrado con {questions} preguntas. {lies}"),
+    ("and", "y"),
+    ("hotseat_title", "¡Adivina el número! Por turnos para {players}."),
+    ("hotseat_shared", "Al mejor de {rounds}. Un número secreto compartido entre {low} y {high}."),
+    ("hotseat_own", "Al mejor de {rounds}. Cada uno tiene su propio número secreto entre {low} y {high}."),
+    ("hotseat_round", "Ronda {round}"),
+    ("hotseat_prompt", "{name}, tu número (intento {attempt}):"),
+    ("hotseat_gives_up", "{name} se rinde en esta ronda."),
+    ("hotseat_guessed", "{name} dijo {guess}: {reply}"),
+    ("hotseat_out_of_attempts", "{name} se ha quedado sin intentos."),
+    ("hotseat_abandoned", "Partida abandonada."),
+    ("hotseat_nobody", "Nadie lo encontró en esta ronda."),
+    ("hotseat_round_winner", "¡{name} gana la ronda {round}!"),
+    ("hotseat_round_tie", "¡La ronda {round} es un empate entre {names}!"),
+    ("hotseat_secret", "El número secreto era {secret}."),
+    ("hotseat_own_secret", "El número secreto de {name} era {secret}."),
+    ("hotseat_score", "Marcador: {scores}"),
+    ("hotseat_match_winner", "¡{name} gana la partida!"),
+    ("hotseat_match_tie", "La partida es un empate entre {names}."),
+    ("hotseat_standing", "{wins} victorias, {guesses} intentos"),
+    ("bulls_title", "¡Toros y vacas!"),
+    ("bulls_code", "Estoy pensando en un código de {digits} cifras distintas."),
+    ("bulls_code_attempts", "Estoy pensando en un código de {digits} cifras distintas. Tienes {attempts} intentos."),
+    ("bulls_no_more_input", "No hay más entrada. El código era {code}."),
+    ("bulls_gave_up", "Te has rendido después de {attempts} intentos. El código era {code}."),
+    ("bulls_no_hints", "No hay pistas para códigos de más de {digits} cifras."),
+    ("bulls_hint", "Pista: todavía son posibles {count} códigos. Prueba {guess}."),
+    ("bulls_out_of_attempts", "¡Sin intentos! El código era {code}."),
+    ("bull", "toro"),
+    ("bulls", "toros"),
+    ("cow", "vaca"),
+    ("cows", "vacas"),
+    ("code_wrong_length", "El código tiene {expected} cifras, no {got}."),
+    ("code_not_a_digit", "'{text}' no es una cifra."),
+    ("code_repeated", "{digit} aparece más de una vez; todas las cifras son distintas."),
+    ("bulls_solver_start", "Descifrando un código de {digits} cifras distintas."),
+    ("bulls_solver_found", "El código es {code}, encontrado en {guesses} intentos."),
+    ("time_attack_title", "¡Contrarreloj! Encuentra tantos números secretos como puedas en {seconds} segundos."),
+    ("time_attack_range", "Cada número secreto está entre {low} y {high}."),
+    ("time_attack_secret", "Secreto n.º {number}."),
+    ("time_attack_prompt", "[quedan {seconds}s] Por favor, introduce tu número."),
+    ("time_attack_no_more_input", "No hay más entrada."),
+    ("time_attack_times_up", "¡Se acabó el tiempo! El número secreto era {secret}."),
+    ("time_attack_score", "Puntuación final: {solved} encontrados, {missed} fallados, {guesses} intentos."),
+    ("time_attack_average", "Eso son {average} intentos por número encontrado."),
+    ("tui_instructions", "Escribe un número y pulsa Intro. :help muestra los comandos."),
+    ("tui_title", "¡Adivina el número! Está entre {low} y {high}."),
+    ("tui_still_possible", " Todavía posible: {low}..={high} "),
+    ("tui_guesses", " Intentos "),
+    ("tui_guess_column", "Número"),
+    ("tui_top", " Top {count} ({difficulty}) "),
+    ("tui_no_wins", "todavía no hay victorias"),
+    ("tui_attempts_left", "Intentos: {attempts}, quedan {left}"),
+    ("tui_attempts_no_limit", "Intentos: {attempts}, sin límite"),
+    ("tui_press_any_key", "Pulsa cualquier tecla para salir."),
+    ("tui_your_guess", " Tu número "),
+    ("decimal_title", "¡Adivina el número! (modo decimal)"),
+    ("decimal_range", "Estoy pensando en un número entre {low} y {high}, con {places} decimales."),
+    ("decimal_range_one_place", "Estoy pensando en un número entre {low} y {high}, con 1 decimal."),
+    ("decimal_rounded", "Has dicho: {guess} (redondeado de {text})"),
+    ("decimal_nan", "NaN no es un número que se pueda adivinar."),
+    ("decimal_infinite", "El infinito nunca es el secreto."),
+];
+
+const DE: Catalog = &[
+    ("title", "Errate die Zahl!"),
+    ("range", "Ich denke an eine Zahl zwischen {low} und {high}."),
+    ("range_attempts", "Ich denke an eine Zahl zwischen {low} und {high}. Du hast {attempts} Versuche."),
+    ("help_hint", "Tippe :help für die Befehle."),
+    ("welcome_back", "Willkommen zurück! Du hast schon {attempts} Mal geraten; :history zeigt deine Tipps."),
+    ("prompt", "Bitte gib deinen Tipp ein."),
+    ("no_more_input", "Keine Eingabe mehr. Die geheime Zahl war {secret}."),
+    ("saved", "Spiel gespeichert. Mit --resume geht es weiter."),
+    ("save_unavailable", "In diesem Spiel kann nicht gespeichert werden."),
+    ("you_guessed", "Dein Tipp: {guess}"),
+    ("too_small", "Zu klein!"),
+    ("too_big", "Zu groß!"),
+    ("you_win", "Du hast gewonnen!"),
+    ("out_of_attempts", "Keine Versuche mehr! Die geheime Zahl war {secret}."),
+    ("goodbye", "Tschüss!"),
+    ("gave_up", "Du hast nach {attempts} Versuchen aufgegeben. Die geheime Zahl war {secret}."),
+    ("hint", "Hinweis: Die geheime Zahl liegt zwischen {low} und {high}."),
+    ("no_guesses", "Noch keine Tipps."),
+    ("unknown_command", "Unbekannter Befehl '{command}'. Tippe :help für eine Liste."),
+    ("command_quit", "aufhören, ohne die geheime Zahl zu verraten"),
+    ("command_hint", "den Bereich zeigen, in dem die Zahl liegen muss"),
+    ("command_history", "deine bisherigen Tipps auflisten"),
+    ("command_giveup", "die geheime Zahl verraten und das Spiel beenden"),
+    ("command_save", "speichern und aufhören; weiter mit --resume"),
+    ("command_help", "diese Liste zeigen"),
+    ("invalid_empty", "Bitte gib eine Zahl ein."),
+    ("invalid_not_a_number", "'{text}' ist keine Zahl."),
+    ("invalid_negative", "{guess} ist negativ; die geheime Zahl ist nie kleiner als null."),
+    ("invalid_overflow", "Diese Zahl ist viel zu groß für die geheime Zahl."),
+    ("invalid_out_of_range", "{guess} liegt außerhalb des Bereichs; rate zwischen {low} und {high}."),
+    ("temperature_burning", "Glühend heiß!"),
+    ("temperature_hot", "Heiß."),
+    ("temperature_warm", "Warm."),
+    ("temperature_cold", "Kalt."),
+    ("temperature_freezing", "Eiskalt."),
+    ("trend_warmer", "Wärmer als dein letzter Tipp."),
+    ("trend_colder", "Kälter als dein letzter Tipp."),
+    ("trend_same", "Genauso weit weg wie dein letzter Tipp."),
+    ("reverse_think", "Denk dir eine Zahl zwischen {low} und {high} aus."),
+    ("reverse_instructions", "Antworte auf jeden Tipp mit higher, lower oder correct (oder :quit)."),
+    ("reverse_is_it", "Ist es {guess}?"),
+    ("reverse_not_an_answer", "'{text}' ist keine Antwort. Tippe higher, lower oder correct."),
+    ("reverse_conflict_higher", "'{answer}' für {guess} widerspricht Antwort #{number}, bei der du gesagt hast, deine Zahl sei größer als {earlier}."),
+    ("reverse_conflict_lower", "'{answer}' für {guess} widerspricht Antwort #{number}, bei der du gesagt hast, deine Zahl sei kleiner als {earlier}."),
+    ("reverse_outside_range", "'{answer}' für {guess} würde deine Zahl aus dem gewählten Bereich werfen."),
+    ("reverse_got_it", "Hab's! Deine Zahl ist {number}. Ich habe {guesses} Tipps gebraucht."),
+    ("you_have_attempts", "Du hast {attempts} Versuche."),
+    ("liar_title", "Errate die Zahl! (Lügenmodus)"),
+    ("liar_range", "Ich denke an eine Zahl zwischen {low} und {high} und darf bis zu {lies} Mal lügen."),
+    ("liar_never_lied", "Ich habe nie gelogen."),
+    ("liar_lied_on", "Ich habe bei den Antworten {answers} gelogen."),
+    ("liar_gave_up", "Die geheime Zahl war {secret}. {lies}"),
+    ("liar_no_hints", "Keine Hinweise, wenn ich lügen könnte!"),
+    ("liar_out_of_attempts", "Keine Versuche mehr! Die geheime Zahl war {secret}. {lies}"),
+    ("liar_solver_start", "Suche eine Zahl zwischen {low} und {high} mit bis zu {lies} Lügen."),
+    ("liar_solver_bound", "Er findet sie sicher in höchstens {questions} Fragen, und keine Strategie schafft das mit weniger."),
+    ("liar_solver_question", "Ist es {guess}? {reply}"),
+    ("liar_solver_found", "Die Zahl ist {secret}, gefunden mit {questions} Fragen. {lies}"),
+    ("and", "und"),
+    ("hotseat_title", "Errate die Zahl! Abwechselnd für {players}."),
+    ("hotseat_shared", "Best of {rounds}. Eine gemeinsame geheime Zahl zwischen {low} und {high}."),
+    ("hotseat_own", "Best of {rounds}. Jeder hat eine eigene geheime Zahl zwischen {low} und {high}."),
+    ("hotseat_round", "Runde {round}"),
+    ("hotseat_prompt", "{name}, dein Tipp (Versuch {attempt}):"),
+    ("hotseat_gives_up", "{name} gibt diese Runde auf."),
+    ("hotseat_guessed", "{name} tippt {guess}: {reply}"),
+    ("hotseat_out_of_attempts", "{name} hat keine Versuche mehr."),
+    ("hotseat_abandoned", "Spiel abgebrochen."),
+    ("hotseat_nobody", "Niemand hat sie in dieser Runde gefunden."),
+    ("hotseat_round_winner", "{name} gewinnt Runde {round}!"),
+    ("hotseat_round_tie", "Runde {round} endet unentschieden zwischen {names}!"),
+    ("hotseat_secret", "Die geheime Zahl war {secret}."),
+    ("hotseat_own_secret", "Die geheime Zahl von {name} war {secret}."),
+    ("hotseat_score", "Stand: {scores}"),
+    ("hotseat_match_winner", "{name} gewinnt das Spiel!"),
+    ("hotseat_match_tie", "Das Spiel endet unentschieden zwischen {names}."),
+    ("hotseat_standing", "{wins} Siege, {guesses} Tipps"),
+    ("bulls_title", "Bullen und Kühe!"),
+    ("bulls_code", "Ich denke an einen Code aus {digits} verschiedenen Ziffern."),
+    ("bulls_code_attempts", "Ich denke an einen Code aus {digits} verschiedenen Ziffern. Du hast {attempts} Versuche."),
+    ("bulls_no_more_input", "Keine Eingabe mehr. Der Code war {code}."),
+    ("bulls_gave_up", "Du hast nach {attempts} Versuchen aufgegeben. Der Code war {code}."),
+    ("bulls_no_hints", "Keine Hinweise für Codes mit mehr als {digits} Ziffern."),
+    ("bulls_hint", "Hinweis: {count} Codes sind noch möglich. Versuch es mit {guess}."),
+    ("bulls_out_of_attempts", "Keine Versuche mehr! Der Code war {code}."),
+    ("bull", "Bulle"),
+    ("bulls", "Bullen"),
+    ("cow", "Kuh"),
+    ("cows", "Kühe"),
+    ("code_wrong_length", "Der Code hat {expected} Ziffern, nicht {got}."),
+    ("code_not_a_digit", "'{text}' ist keine Ziffer."),
+    ("code_repeated", "{digit} kommt mehr als einmal vor; alle Ziffern sind verschieden."),
+    ("bulls_solver_start", "Knacke einen Code aus {digits} verschiedenen Ziffern."),
+    ("bulls_solver_found", "Der Code ist {code}, gefunden in {guesses} Versuchen."),
+    ("time_attack_title", "Zeitrennen! Finde so viele geheime Zahlen wie möglich in {seconds} Sekunden."),
+    ("time_attack_range", "Jede geheime Zahl liegt zwischen {low} und {high}."),
+    ("time_attack_secret", "Geheime Zahl Nr. {number}."),
+    ("time_attack_prompt", "[noch {seconds}s] Bitte gib deinen Tipp ein."),
+    ("time_attack_no_more_input", "Keine Eingabe mehr."),
+    ("time_attack_times_up", "Die Zeit ist um! Die geheime Zahl war {secret}."),
+    ("time_attack_score", "Endstand: {solved} gefunden, {missed} verpasst, {guesses} Tipps."),
+    ("time_attack_average", "Das sind {average} Tipps pro gefundener Zahl."),
+    ("tui_instructions", "Tippe eine Zahl und drücke Enter. :help zeigt die Befehle."),
+    ("tui_title", "Errate die Zahl! Sie liegt zwischen {low} und {high}."),
+    ("tui_still_possible", " Noch möglich: {low}..={high} "),
+    ("tui_guesses", " Tipps "),
+    ("tui_guess_column", "Tipp"),
+    ("tui_top", " Top {count} ({difficulty}) "),
+    ("tui_no_wins", "noch keine Siege"),
+    ("tui_attempts_left", "Versuche: {attempts}, noch {left}"),
+    ("tui_attempts_no_limit", "Versuche: {attempts}, unbegrenzt"),
+    ("tui_press_any_key", "Drücke eine beliebige Taste zum Beenden."),
+    ("tui_your_guess", " Dein Tipp "),
+    ("decimal_title", "Errate die Zahl! (Dezimalmodus)"),
+    ("decimal_range", "Ich denke an eine Zahl zwischen {low} und {high}, auf {places} Nachkommastellen."),
+    ("decimal_range_one_place", "Ich denke an eine Zahl zwischen {low} und {high}, auf 1 Nachkommastelle."),
+    ("decimal_rounded", "Dein Tipp: {guess} (gerundet von {text})"),
+    ("decimal_nan", "NaN ist keine Zahl, die man raten kann."),
+    ("decimal_infinite", "Unendlich ist nie die geheime Zahl."),
+];
+
+impl Locale {
+    pub const ALL: [Locale; 3] = [Locale::En, Locale::Es, Locale::De];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Es => "es",
+            Locale::De => "de",
+        }
+    }
+
+    pub fn catalog(self) -> Catalog {
+        match self {
+            Locale::En => EN,
+            Locale::Es => ES,
+            Locale::De => DE,
+        }
+    }
+
+    // the locale's own text for a key, without falling back
+    pub fn lookup(self, key: &str) -> Option<&'static str> {
+        self.catalog().iter().find(|(k, _)| *k == key).map(|(_, text)| *text)
+    }
+
+    // falls back to English, and then to the key itself, rather than print nothing.
+    // a key English lacks is a typo in the code, so debug builds stop on it instead.
+    pub fn text(self, key: &'static str) -> &'static str {
+        let english = Locale::En.lookup(key);
+        debug_assert!(english.is_some(), "no message for the key {:?}", key);
+        self.lookup(key).or(english).unwrap_or(key)
+    }
+
+    // the text with each {name} replaced by its value
+    pub fn format(self, key: &'static str, args: &[(&str, &dyn fmt::Display)]) -> String {
+        args.iter().fold(self.text(key).to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{}}}", name), &value.to_string())
+        })
+    }
+
+    // what the player is told after a guess
+    pub fn describe(self, ordering: Ordering) -> &'static str {
+        match ordering {
+            Ordering::Less => self.text("too_small"),
+            Ordering::Greater => self.text("too_big"),
+            Ordering::Equal => self.text("you_win"),
+        }
+    }
+
+    // LC_ALL, then LC_MESSAGES, then LANG, as POSIX orders them. C, POSIX and anything unknown
+    // are English.
+    pub fn from_env() -> Locale {
+        Locale::from_env_var().map(|(_, locale)| locale).unwrap_or_default()
+    }
+
+    // the same, along with the variable that decided it; None when none of them names a known language
+    pub fn from_env_var() -> Option<(&'static str, Locale)> {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .into_iter()
+            .filter_map(|name| env::var(name).ok().map(|value| (name, value)))
+            .find(|(_, value)| !value.is_empty())
+            .and_then(|(name, value)| value.parse().ok().map(|locale| (name, locale)))
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for Locale {
+    type Err = String;
+
+    // takes "de", "de_DE", "de-AT" or "de_DE.UTF-8" alike
+    fn from_str(s: &str) -> Result<Locale, String> {
+        let language = s
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or("")
+            .to_lowercase();
+        Locale::ALL
+            .into_iter()
+            .find(|locale| locale.code() == language)
+            .ok_or_else(|| format!("unknown language: {} (expected en, es or de)", s))
+    }
+}
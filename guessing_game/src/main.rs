@@ -1,40 +1,667 @@
-use std::io;
-use std::cmp::Ordering;
-use rand::Rng;
+use std::env;
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::process;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use guessing_game::cli::{self, Command, Options};
+use guessing_game::clock::SystemClock;
+use guessing_game::daily::{self, DailyLog};
+use guessing_game::events::{self, Event, EventLog};
+use guessing_game::history::{GameRecord, History};
+use guessing_game::leaderboard::{Entry, Leaderboard, Variant};
+use guessing_game::rating::{self, Ratings};
+use guessing_game::save::SavedGame;
+use guessing_game::stats::PlayerStats;
+use guessing_game::{api, bulls, config, decimal, hotseat, liar, multiplayer, reverse, simulate, storage, strategy, timeattack};
+use guessing_game::{play_game, Difficulty, Game, Session, Settings, Status, Summary};
 
 fn main() {
-    guess_the_number();
+    let flags = cli::parse_args(env::args().skip(1)).unwrap_or_else(|e| exit_with(&e));
+    if !config::applies_to(flags.command) {
+        run(&flags);
+        return;
+    }
+    let (config, config_path) = config::load(&flags).unwrap_or_else(|e| exit_with(&e));
+    let mut options = flags.clone();
+    config::apply(&mut options, &config).unwrap_or_else(|e| exit_with(&e));
+
+    match options.command {
+        // `config show` reports on the flags as given, before the config was merged into them
+        Command::ConfigShow => config::show(io::stdout(), &flags, &config, &config_path)
+            .unwrap_or_else(|e| exit_with(&e.to_string())),
+        _ => run(&options),
+    }
 }
 
-fn guess_the_number() {
-    println!("Guess the number!");
+fn run(options: &Options) {
+    match options.command {
+        Command::Play => play(options),
+        Command::Leaderboard => show_leaderboard(options),
+        Command::Reverse => play_reverse(options),
+        Command::Simulate => run_simulation(options),
+        Command::Solve => run_solver(options),
+        Command::Serve => run_server(options),
+        Command::HotSeat => play_hot_seat(options),
+        Command::Bulls => play_bulls(options),
+        Command::TimeAttack => play_time_attack(options),
+        Command::Replay => run_replay(options),
+        Command::Api => run_api(options),
+        Command::Stats => show_stats(options),
+        Command::Decimal => play_decimal(options),
+        Command::ConfigShow => unreachable!("config show is handled in main"),
+    }
+}
 
-    let secret_number = rand::thread_rng().gen_range(1..101);
+fn play(options: &Options) {
+    if options.tui {
+        if options.lies.is_some() {
+            exit_with("--tui can't be combined with --lies");
+        }
+        if !io::stdout().is_terminal() || !io::stdin().is_terminal() {
+            exit_with("--tui needs a terminal; leave it off when piping");
+        }
+    }
 
-    loop {
-        println!("Please input your guess.");
+    if options.daily {
+        if options.resume {
+            exit_with("--daily can't be combined with --resume");
+        }
+        play_daily(options);
+    }
 
-        let mut guess = String::new();
+    if options.resume {
+        resume(options);
+    }
+
+    let settings = if options.adaptive {
+        adaptive_settings(options)
+    } else {
+        options.settings().unwrap_or_else(|e| exit_with(&e))
+    };
+    let seed = cli::resolve_seed(options).unwrap_or_else(|e| exit_with(&e));
+
+    // printing the seed means any session can be replayed with --seed
+    println!("Seed: {}", seed);
+    let mut rng = StdRng::seed_from_u64(seed);
 
-        io::stdin()
-        .read_line(&mut guess)
-        .expect("Failed to read line");
+    let stdin = io::stdin();
+
+    if let Some(lies) = options.lies {
+        liar::validate(&settings).unwrap_or_else(|e| exit_with(&e));
+        let summary = liar::liar_game(stdin.lock(), io::stdout(), settings, lies, &mut rng, options.locale())
+            .expect("Failed to play the game");
+        // liar games are not comparable with the others, so they stay off the leaderboard
+        process::exit(summary.status.exit_code());
+    }
 
-        let guess: u32 = match guess.trim().parse() {
-            Ok(num) => num,
-            Err(_) => continue,
-        };
-        
-        println!("You guessed: {}", guess);
+    let game = Game::new(settings, &mut rng);
+    finish(options, game, seed, Duration::ZERO);
+}
+
+// picks up the game written by :save or Ctrl-C
+fn resume(options: &Options) -> ! {
+    let path = SavedGame::default_path();
+    let saved = SavedGame::load(&path)
+        .unwrap_or_else(|e| exit_with(&format!("could not resume from {}: {}", path.display(), e)));
+
+    // a save is used up once it is resumed; :save or Ctrl-C write a fresh one
+    if let Err(e) = fs::remove_file(&path) {
+        eprintln!("guessing_game: could not remove {}: {}", path.display(), e);
+    }
 
-        match guess.cmp(&secret_number) {
-            Ordering::Less => println!("Too small!"),
-            Ordering::Greater => println!("Too big!"),
-            Ordering::Equal => {
-                println!("You win!");
-                break;
+    println!("Seed: {}", saved.seed);
+    finish(options, saved.restore(), saved.seed, saved.elapsed());
+}
+
+// the range and attempts that suit the player's rating
+fn adaptive_settings(options: &Options) -> Settings {
+    if options.difficulty.is_some() || options.low.is_some() || options.high.is_some() || options.attempts.is_some() {
+        exit_with("--adaptive picks the range and attempts itself");
+    }
+    if options.lies.is_some() {
+        exit_with("--adaptive can't be combined with --lies");
+    }
+
+    let path = Ratings::default_path();
+    let ratings = Ratings::load(&path)
+        .unwrap_or_else(|e| exit_with(&format!("could not read {}: {}", path.display(), e)));
+    let player = options.player.clone().unwrap_or_else(storage::default_player);
+    let rating = ratings.get(&player).rating;
+
+    let settings = Settings {
+        temperature: options.temperature,
+        ..rating::settings_for(rating)
+    };
+    println!(
+        "{}'s rating is {:.0}, so this game is {}..={} with {} attempts.",
+        player,
+        rating,
+        settings.low,
+        settings.high,
+        settings.max_attempts.unwrap_or(0)
+    );
+    settings
+}
+
+// plays a new or resumed number game to the end, saving it if the player presses Ctrl-C
+fn finish(options: &Options, mut game: Game, seed: u64, elapsed: Duration) -> ! {
+    let settings = *game.settings();
+    let save_path = SavedGame::default_path();
+    let checkpoint = Arc::new(Mutex::new(None));
+
+    let log = open_log(options, seed);
+
+    let handler_checkpoint = Arc::clone(&checkpoint);
+    let handler_path = save_path.clone();
+    let handler_log = log.clone();
+    ctrlc::set_handler(move || {
+        let saved: Option<SavedGame> = handler_checkpoint.lock().unwrap().take();
+        match saved {
+            Some(saved) => match saved.save(&handler_path) {
+                Ok(()) => {
+                    if let Some(log) = &handler_log {
+                        log.lock().unwrap().record(&Event::quit(&saved.restore(), "saved"));
+                    }
+                    println!("\nGame saved. Pick it up again with --resume.")
+                }
+                Err(e) => eprintln!("\nguessing_game: could not save the game: {}", e),
             },
+            None => println!(),
+        }
+        process::exit(Status::Quit.exit_code());
+    })
+    .expect("Failed to set the Ctrl-C handler");
+
+    let session = Session {
+        seed,
+        save_path: Some(save_path.clone()),
+        elapsed,
+        checkpoint: Some(checkpoint),
+        log,
+        locale: options.locale(),
+        color: config::color(options),
+    };
+
+    let summary = play_number(options, &mut game, &session);
+    record_history(options, &game, &summary);
+    update_rating(options, &game);
+
+    if summary.status == Status::Won {
+        record_win(options, Variant::Number, settings.difficulty, (settings.low, settings.high), &summary);
+    }
+
+    process::exit(summary.status.exit_code());
+}
+
+// the daily puzzle: the same secret for everyone today, one go per player
+fn play_daily(options: &Options) -> ! {
+    if options.seed.is_some() {
+        exit_with("--daily picks its own secret, so it can't be combined with --seed");
+    }
+    if options.lies.is_some() {
+        exit_with("--daily can't be combined with --lies");
+    }
+    if options.difficulty.is_some() || options.low.is_some() || options.high.is_some() || options.attempts.is_some() {
+        exit_with("--daily always uses the normal settings");
+    }
+
+    let date = daily::today();
+    let player = options.player.clone().unwrap_or_else(storage::default_player);
+    let path = DailyLog::default_path();
+    let mut log = DailyLog::load(&path)
+        .unwrap_or_else(|e| exit_with(&format!("could not read {}: {}", path.display(), e)));
+
+    if let Some(played) = log.find(&player, &date) {
+        println!("{} has already played the daily puzzle for {}. Come back tomorrow!", player, date);
+        if let Some(share) = &played.share {
+            println!();
+            println!("{}", share);
+        }
+        process::exit(Status::Quit.exit_code());
+    }
+
+    // the day is claimed before playing, so quitting or Ctrl-C can't be used to peek and retry
+    log.record(&player, &date, None);
+    log.save(&path)
+        .unwrap_or_else(|e| exit_with(&format!("could not save {}: {}", path.display(), e)));
+
+    println!("Daily puzzle for {}", date);
+    let settings = daily::settings();
+    let mut game = Game::with_secret(settings, daily::secret(&date, &settings));
+
+    // no save path: a daily game can't be put aside and resumed
+    let seed = daily::seed(&date);
+    let session = Session {
+        seed,
+        log: open_log(options, seed),
+        locale: options.locale(),
+        color: config::color(options),
+        ..Session::default()
+    };
+    let summary = play_number(options, &mut game, &session);
+    record_history(options, &game, &summary);
+    update_rating(options, &game);
+
+    let share = daily::share(&date, &game);
+    log.record(&player, &date, Some(share.clone()));
+    if let Err(e) = log.save(&path) {
+        eprintln!("guessing_game: could not save {}: {}", path.display(), e);
+    }
+
+    println!();
+    println!("{}", share);
+
+    if summary.status == Status::Won {
+        record_win(options, Variant::Number, settings.difficulty, (settings.low, settings.high), &summary);
+    }
+
+    process::exit(summary.status.exit_code());
+}
+
+// every number game writes an event log for `replay`, keeping the last few unless --log says where.
+// only a --log that can't be written is fatal.
+fn open_log(options: &Options, seed: u64) -> Option<Arc<Mutex<EventLog>>> {
+    let path = match &options.log {
+        Some(path) => PathBuf::from(path),
+        None => {
+            // make room for this one
+            if let Err(e) = events::prune(events::KEEP - 1) {
+                eprintln!("guessing_game: could not remove old logged games: {}", e);
+            }
+            EventLog::default_path(seed)
+        }
+    };
+    match EventLog::create(&path) {
+        Ok(log) => Some(Arc::new(Mutex::new(log))),
+        Err(e) if options.log.is_some() => exit_with(&format!("could not write {}: {}", path.display(), e)),
+        Err(e) => {
+            eprintln!("guessing_game: could not write {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+// plays the number game line by line, or full-screen with --tui
+fn play_number(options: &Options, game: &mut Game, session: &Session) -> Summary {
+    if options.tui {
+        play_tui(game, session)
+    } else {
+        let stdin = io::stdin();
+        play_game(stdin.lock(), io::stdout(), game, session).expect("Failed to play the game")
+    }
+}
+
+#[cfg(feature = "tui")]
+fn play_tui(game: &mut Game, session: &Session) -> Summary {
+    let path = Leaderboard::default_path();
+    let leaderboard = Leaderboard::load(&path)
+        .unwrap_or_else(|e| exit_with(&format!("could not read {}: {}", path.display(), e)));
+    guessing_game::tui::play_game(game, session, &leaderboard).expect("Failed to play the game")
+}
+
+#[cfg(not(feature = "tui"))]
+fn play_tui(_game: &mut Game, _session: &Session) -> Summary {
+    exit_with("this build has no terminal UI; rebuild with the tui feature")
+}
+
+fn play_bulls(options: &Options) {
+    let seed = cli::resolve_seed(options).unwrap_or_else(|e| exit_with(&e));
+    println!("Seed: {}", seed);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    if options.solver {
+        let digits = options.digits.unwrap_or(4);
+        bulls::validate_solver(digits).unwrap_or_else(|e| exit_with(&e));
+        bulls::solve_game(io::stdout(), digits, &mut rng, options.locale()).expect("Failed to run the solver");
+        return;
+    }
+
+    let settings = options.bulls_settings().unwrap_or_else(|e| exit_with(&e));
+    let stdin = io::stdin();
+    let summary = bulls::bulls_and_cows(stdin.lock(), io::stdout(), settings, &mut rng, options.locale())
+        .expect("Failed to play the game");
+
+    if summary.status == Status::Won {
+        record_win(options, Variant::BullsAndCows, settings.difficulty, settings.range(), &summary);
+    }
+
+    process::exit(summary.status.exit_code());
+}
+
+// adds a win to the leaderboard and shows the table it landed in
+fn record_win(
+    options: &Options,
+    variant: Variant,
+    difficulty: Difficulty,
+    (low, high): (i128, i128),
+    summary: &Summary,
+) {
+    let path = Leaderboard::default_path();
+    let mut leaderboard = Leaderboard::load(&path)
+        .unwrap_or_else(|e| exit_with(&format!("could not read {}: {}", path.display(), e)));
+
+    leaderboard.record(Entry {
+        player: options.player.clone().unwrap_or_else(storage::default_player),
+        variant,
+        difficulty,
+        low,
+        high,
+        attempts: summary.attempts,
+        duration_ms: summary.elapsed.as_millis() as u64,
+        timestamp: storage::now(),
+    });
+
+    // losing the leaderboard shouldn't spoil a win, so only warn
+    if let Err(e) = leaderboard.save(&path) {
+        eprintln!("guessing_game: could not save {}: {}", path.display(), e);
+    }
+
+    println!();
+    leaderboard
+        .print_top(&mut io::stdout(), variant, difficulty)
+        .expect("Failed to print the leaderboard");
+}
+
+// adds a finished game to the history that `stats` reads
+fn record_history(options: &Options, game: &Game, summary: &Summary) {
+    let player = options.player.clone().unwrap_or_else(storage::default_player);
+    let record = match GameRecord::from_game(&player, game, summary.elapsed.as_millis() as u64) {
+        Some(record) => record,
+        None => return,
+    };
+
+    let path = History::default_path();
+    // like the leaderboard, the history is a nice-to-have, so problems with it only warn
+    let result = History::load(&path).and_then(|mut history| {
+        history.record(record);
+        history.save(&path)
+    });
+    if let Err(e) = result {
+        eprintln!("guessing_game: could not update {}: {}", path.display(), e);
+    }
+}
+
+// moves the player's rating after a finished game and says by how much
+fn update_rating(options: &Options, game: &Game) {
+    if !matches!(game.status(), Status::Won | Status::Lost | Status::GaveUp) {
+        return;
+    }
+
+    let path = Ratings::default_path();
+    let mut ratings = match Ratings::load(&path) {
+        Ok(ratings) => ratings,
+        Err(e) => {
+            eprintln!("guessing_game: could not read {}: {}", path.display(), e);
+            return;
+        }
+    };
+    let player = options.player.clone().unwrap_or_else(storage::default_player);
+    let (before, after) = ratings.record(&player, game);
+    if let Err(e) = ratings.save(&path) {
+        eprintln!("guessing_game: could not save {}: {}", path.display(), e);
+    }
+
+    println!("Rating: {:.0} -> {:.0} ({:+.0})", before.rating, after.rating, after.rating - before.rating);
+}
+
+fn play_time_attack(options: &Options) {
+    let settings = options.settings().unwrap_or_else(|e| exit_with(&e));
+    let seed = cli::resolve_seed(options).unwrap_or_else(|e| exit_with(&e));
+    let budget = options
+        .time
+        .map(Duration::from_secs)
+        .unwrap_or(timeattack::DEFAULT_BUDGET);
+
+    println!("Seed: {}", seed);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let score = timeattack::time_attack(
+        io::BufReader::new(io::stdin()),
+        io::stdout(),
+        settings,
+        budget,
+        SystemClock::start(),
+        &mut rng,
+        options.locale(),
+    )
+    .expect("Failed to play the game");
+
+    // running out of time without finding a single secret is a loss
+    let status = match (score.finished, score.solved) {
+        (false, _) => Status::Quit,
+        (true, 0) => Status::Lost,
+        (true, _) => Status::Won,
+    };
+    process::exit(status.exit_code());
+}
+
+fn run_replay(options: &Options) {
+    let path = match &options.log {
+        Some(path) => PathBuf::from(path),
+        None => events::latest()
+            .unwrap_or_else(|e| exit_with(&format!("could not look for logged games: {}", e)))
+            .unwrap_or_else(|| exit_with("no logged games yet; play one first or pass --log")),
+    };
+    let events = events::load(&path)
+        .unwrap_or_else(|e| exit_with(&format!("could not read {}: {}", path.display(), e)));
+
+    println!("Replaying {}", path.display());
+    events::replay(io::stdout(), &events, options.speed.unwrap_or(1.0)).expect("Failed to replay the game");
+}
+
+fn play_decimal(options: &Options) {
+    if options.temperature {
+        exit_with("decimal mode can't be combined with --hot-cold");
+    }
+    let settings = options.decimal_settings().unwrap_or_else(|e| exit_with(&e));
+    let seed = cli::resolve_seed(options).unwrap_or_else(|e| exit_with(&e));
+    println!("Seed: {}", seed);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let stdin = io::stdin();
+    let summary = decimal::decimal_game(stdin.lock(), io::stdout(), settings, &mut rng, options.locale())
+        .expect("Failed to play the game");
+    // decimal games aren't comparable with whole-number ones, so they stay off the leaderboard
+    process::exit(summary.status.exit_code());
+}
+
+fn play_reverse(options: &Options) {
+    let settings = options.settings().unwrap_or_else(|e| exit_with(&e));
+    let stdin = io::stdin();
+
+    let found = reverse::reverse_game(stdin.lock(), io::stdout(), settings, options.locale())
+        .expect("Failed to play the game");
+
+    let status = if found.is_some() { Status::Won } else { Status::Quit };
+    process::exit(status.exit_code());
+}
+
+fn run_simulation(options: &Options) {
+    // enough games for the averages to settle without making the command slow
+    const DEFAULT_GAMES: usize = 1000;
+
+    let settings = options.settings().unwrap_or_else(|e| exit_with(&e));
+    let seed = cli::resolve_seed(options).unwrap_or_else(|e| exit_with(&e));
+    let games = options.games.unwrap_or(DEFAULT_GAMES);
+
+    let mut strategies = if options.strategies.is_empty() {
+        strategy::all()
+    } else {
+        options
+            .strategies
+            .iter()
+            .map(|name| strategy::by_name(name))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap_or_else(|e| exit_with(&e))
+    };
+
+    println!("Seed: {}", seed);
+    println!(
+        "Simulating {} games per strategy between {} and {}.",
+        games, settings.low, settings.high
+    );
+
+    let mut stdout = io::stdout();
+    for stats in simulate::simulate(&mut strategies, settings, games, seed) {
+        println!();
+        stats.print(&mut stdout).expect("Failed to print the results");
+    }
+}
+
+fn run_solver(options: &Options) {
+    let settings = options.settings().unwrap_or_else(|e| exit_with(&e));
+    liar::validate(&settings).unwrap_or_else(|e| exit_with(&e));
+    let lies = options.lies.unwrap_or(1);
+    liar::validate_solver(&settings, lies).unwrap_or_else(|e| exit_with(&e));
+    let seed = cli::resolve_seed(options).unwrap_or_else(|e| exit_with(&e));
+
+    println!("Seed: {}", seed);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    liar::solve_game(io::stdout(), settings, lies, &mut rng, options.locale())
+        .expect("Failed to run the solver");
+}
+
+fn play_hot_seat(options: &Options) {
+    let settings = options.settings().unwrap_or_else(|e| exit_with(&e));
+    let rounds = options.rounds.unwrap_or(1);
+    hotseat::validate(&options.players, rounds).unwrap_or_else(|e| exit_with(&e));
+    let seed = cli::resolve_seed(options).unwrap_or_else(|e| exit_with(&e));
+
+    println!("Seed: {}", seed);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let stdin = io::stdin();
+    let standings = hotseat::hot_seat(
+        stdin.lock(),
+        io::stdout(),
+        settings,
+        &options.players,
+        options.shared,
+        rounds,
+        &mut rng,
+        options.locale(),
+    )
+    .expect("Failed to play the match");
+
+    let status = if standings.is_some() { Status::Won } else { Status::Quit };
+    process::exit(status.exit_code());
+}
+
+fn run_server(options: &Options) {
+    let settings = options.settings().unwrap_or_else(|e| exit_with(&e));
+    let seed = cli::resolve_seed(options).unwrap_or_else(|e| exit_with(&e));
+    let port = options.port.unwrap_or(multiplayer::DEFAULT_PORT);
+
+    // only local players: this is a classroom toy, not an internet service
+    let listener = TcpListener::bind(("127.0.0.1", port))
+        .unwrap_or_else(|e| exit_with(&format!("could not listen on port {}: {}", port, e)));
+    let address = listener.local_addr().expect("Failed to read the listening address");
+
+    println!("Seed: {}", seed);
+    println!("Listening on {}", address);
+    io::stdout().flush().expect("Failed to flush stdout");
+
+    multiplayer::serve(listener, settings, seed).unwrap_or_else(|e| exit_with(&e.to_string()));
+}
+
+fn run_api(options: &Options) {
+    let settings = options.settings().unwrap_or_else(|e| exit_with(&e));
+    let seed = cli::resolve_seed(options).unwrap_or_else(|e| exit_with(&e));
+    let port = options.port.unwrap_or(api::DEFAULT_PORT);
+    let ttl = options.ttl.map(Duration::from_secs).unwrap_or(api::DEFAULT_TTL);
+    if ttl.is_zero() {
+        exit_with("--ttl must be at least 1 second");
+    }
+
+    // local only, like `serve`
+    let listener = TcpListener::bind(("127.0.0.1", port))
+        .unwrap_or_else(|e| exit_with(&format!("could not listen on port {}: {}", port, e)));
+    let address = listener.local_addr().expect("Failed to read the listening address");
+
+    println!("Seed: {}", seed);
+    println!("Listening on http://{}", address);
+    io::stdout().flush().expect("Failed to flush stdout");
+
+    api::serve(listener, settings, seed, ttl, SystemClock::start())
+        .unwrap_or_else(|e| exit_with(&e.to_string()));
+}
+
+fn show_leaderboard(options: &Options) {
+    let path = Leaderboard::default_path();
+    let leaderboard = Leaderboard::load(&path)
+        .unwrap_or_else(|e| exit_with(&format!("could not read {}: {}", path.display(), e)));
+
+    // --difficulty narrows the listing to a single table
+    let difficulties = match options.difficulty {
+        Some(difficulty) => vec![difficulty],
+        None => Difficulty::ALL.to_vec(),
+    };
+
+    // bulls and cows tables only show up once someone has won a game of it
+    let variants = Variant::ALL
+        .into_iter()
+        .filter(|&variant| variant == Variant::Number || leaderboard.has_entries(variant));
+
+    let mut stdout = io::stdout();
+    let mut first = true;
+    for variant in variants {
+        for &difficulty in &difficulties {
+            if !first {
+                println!();
+            }
+            first = false;
+            leaderboard
+                .print_top(&mut stdout, variant, difficulty)
+                .expect("Failed to print the leaderboard");
+        }
+    }
+}
+
+fn show_stats(options: &Options) {
+    let path = History::default_path();
+    let history = History::load(&path)
+        .unwrap_or_else(|e| exit_with(&format!("could not read {}: {}", path.display(), e)));
+
+    // --name narrows the report to one player
+    let players = match &options.player {
+        Some(player) => vec![player.as_str()],
+        None => history.players(),
+    };
+    if players.is_empty() {
+        println!("No finished games yet.");
+        return;
+    }
+
+    let ratings_path = Ratings::default_path();
+    let ratings = Ratings::load(&ratings_path)
+        .unwrap_or_else(|e| exit_with(&format!("could not read {}: {}", ratings_path.display(), e)));
+
+    let mut stdout = io::stdout();
+    for (i, player) in players.into_iter().enumerate() {
+        if i > 0 {
+            println!();
         }
+        let games = history.games_of(player);
+        if games.is_empty() {
+            println!("{} hasn't finished a game yet.", player);
+            continue;
+        }
+        PlayerStats::from_games(player, &games)
+            .print(&mut stdout)
+            .expect("Failed to print the stats");
+        let rating = ratings.get(player);
+        println!("  rating            {:.0} after {} rated games", rating.rating, rating.games);
     }
+}
 
+// exit code 2 is reserved for bad arguments; see Status::exit_code for the others
+fn exit_with(message: &str) -> ! {
+    eprintln!("guessing_game: {}", message);
+    process::exit(2);
 }
@@ -0,0 +1,440 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use crate::difficulty::Settings;
+use crate::game::{Game, Outcome};
+use crate::input;
+
+// the port the server listens on and the client connects to unless told otherwise
+pub const DEFAULT_PORT: u16 = 7878;
+
+// a line sent from a client to the server
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClientMessage {
+    // JOIN <room> <name>; the name is the rest of the line and may contain spaces
+    Join { room: String, name: String },
+    // GUESS <number>, kept as text so the server can say exactly what was wrong with it
+    Guess(String),
+    Quit,
+}
+
+impl ClientMessage {
+    pub fn parse(line: &str) -> Result<ClientMessage, String> {
+        let line = line.trim();
+        let (verb, rest) = line.split_once(' ').unwrap_or((line, ""));
+
+        match verb {
+            "JOIN" => match rest.trim().split_once(' ') {
+                Some((room, name)) if !name.trim().is_empty() => Ok(ClientMessage::Join {
+                    room: room.to_string(),
+                    name: name.trim().to_string(),
+                }),
+                _ => Err(String::from("usage: JOIN <room> <name>")),
+            },
+            "GUESS" => Ok(ClientMessage::Guess(rest.trim().to_string())),
+            "QUIT" => Ok(ClientMessage::Quit),
+            _ => Err(format!("unknown message: {}", verb)),
+        }
+    }
+}
+
+impl fmt::Display for ClientMessage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ClientMessage::Join { room, name } => write!(f, "JOIN {} {}", room, name),
+            ClientMessage::Guess(guess) => write!(f, "GUESS {}", guess),
+            ClientMessage::Quit => write!(f, "QUIT"),
+        }
+    }
+}
+
+// a line sent from the server to one client or broadcast to a whole room
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServerMessage {
+    Welcome { room: String, low: i128, high: i128 },
+    Joined { name: String },
+    Left { name: String },
+    // someone's guess and how it compared to the secret
+    Guess { name: String, guess: i128, ordering: Ordering },
+    Winner { name: String, secret: i128, attempts: u32 },
+    // a fresh secret after a win
+    NewRound { low: i128, high: i128 },
+    Error(String),
+}
+
+fn ordering_name(ordering: Ordering) -> &'static str {
+    match ordering {
+        Ordering::Less => "LESS",
+        Ordering::Greater => "GREATER",
+        Ordering::Equal => "EQUAL",
+    }
+}
+
+fn parse_field<T: std::str::FromStr>(field: Option<&str>) -> Result<T, String> {
+    field
+        .and_then(|field| field.parse().ok())
+        .ok_or_else(|| String::from("malformed message"))
+}
+
+impl ServerMessage {
+    // the wire format: a verb followed by space separated fields, with any name last
+    pub fn to_line(&self) -> String {
+        match self {
+            ServerMessage::Welcome { room, low, high } => format!("WELCOME {} {} {}", low, high, room),
+            ServerMessage::Joined { name } => format!("JOINED {}", name),
+            ServerMessage::Left { name } => format!("LEFT {}", name),
+            ServerMessage::Guess { name, guess, ordering } => {
+                format!("GUESS {} {} {}", guess, ordering_name(*ordering), name)
+            }
+            ServerMessage::Winner { name, secret, attempts } => {
+                format!("WINNER {} {} {}", secret, attempts, name)
+            }
+            ServerMessage::NewRound { low, high } => format!("ROUND {} {}", low, high),
+            ServerMessage::Error(message) => format!("ERROR {}", message),
+        }
+    }
+
+    pub fn parse(line: &str) -> Result<ServerMessage, String> {
+        let line = line.trim_end();
+        let (verb, rest) = line.split_once(' ').unwrap_or((line, ""));
+
+        match verb {
+            "WELCOME" => {
+                let mut fields = rest.splitn(3, ' ');
+                Ok(ServerMessage::Welcome {
+                    low: parse_field(fields.next())?,
+                    high: parse_field(fields.next())?,
+                    room: parse_field(fields.next())?,
+                })
+            }
+            "JOINED" => Ok(ServerMessage::Joined { name: rest.to_string() }),
+            "LEFT" => Ok(ServerMessage::Left { name: rest.to_string() }),
+            "GUESS" => {
+                let mut fields = rest.splitn(3, ' ');
+                let guess = parse_field(fields.next())?;
+                let ordering = match fields.next() {
+                    Some("LESS") => Ordering::Less,
+                    Some("GREATER") => Ordering::Greater,
+                    Some("EQUAL") => Ordering::Equal,
+                    _ => return Err(String::from("malformed message")),
+                };
+                Ok(ServerMessage::Guess {
+                    guess,
+                    ordering,
+                    name: parse_field(fields.next())?,
+                })
+            }
+            "WINNER" => {
+                let mut fields = rest.splitn(3, ' ');
+                Ok(ServerMessage::Winner {
+                    secret: parse_field(fields.next())?,
+                    attempts: parse_field(fields.next())?,
+                    name: parse_field(fields.next())?,
+                })
+            }
+            "ROUND" => {
+                let mut fields = rest.splitn(2, ' ');
+                Ok(ServerMessage::NewRound {
+                    low: parse_field(fields.next())?,
+                    high: parse_field(fields.next())?,
+                })
+            }
+            "ERROR" => Ok(ServerMessage::Error(rest.to_string())),
+            _ => Err(format!("unknown message: {}", verb)),
+        }
+    }
+}
+
+// how the client shows each message to a person
+impl fmt::Display for ServerMessage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ServerMessage::Welcome { room, low, high } => write!(
+                f,
+                "Welcome to room {}! Race the others to a number between {} and {}.",
+                room, low, high
+            ),
+            ServerMessage::Joined { name } => write!(f, "{} joined the room.", name),
+            ServerMessage::Left { name } => write!(f, "{} left the room.", name),
+            ServerMessage::Guess { name, guess, ordering } => {
+                write!(f, "{} guessed {}: {}", name, guess, crate::describe(*ordering))
+            }
+            ServerMessage::Winner { name, secret, attempts } => write!(
+                f,
+                "{} wins! The secret number was {} ({} guesses in the room).",
+                name, secret, attempts
+            ),
+            ServerMessage::NewRound { low, high } => {
+                write!(f, "New round! A new number between {} and {}.", low, high)
+            }
+            ServerMessage::Error(message) => write!(f, "Error: {}", message),
+        }
+    }
+}
+
+// a player's connection, locked on its own so a slow reader only holds up the writes meant for them
+type Connection = Arc<Mutex<TcpStream>>;
+
+// a player who stops reading is given this long before their write fails and they are dropped
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+
+struct Player {
+    id: usize,
+    stream: Connection,
+}
+
+struct Room {
+    game: Game,
+    players: Vec<Player>,
+}
+
+impl Room {
+    // who a room-wide message goes to, taken while the lobby is locked and written to after
+    fn recipients(&self) -> Vec<(usize, Connection)> {
+        self.players
+            .iter()
+            .map(|player| (player.id, Arc::clone(&player.stream)))
+            .collect()
+    }
+}
+
+// everything shared between the connection threads
+struct Lobby {
+    settings: Settings,
+    rng: StdRng,
+    rooms: HashMap<String, Room>,
+    next_id: usize,
+}
+
+// accepts players until the listener fails, one thread per connection.
+// every room races on its own secret, all drawn from the seeded rng.
+pub fn serve(listener: TcpListener, settings: Settings, seed: u64) -> io::Result<()> {
+    // a race has no attempt limit: the first to find the secret wins
+    let settings = Settings {
+        max_attempts: None,
+        ..settings
+    };
+    let lobby = Arc::new(Mutex::new(Lobby {
+        settings,
+        rng: StdRng::seed_from_u64(seed),
+        rooms: HashMap::new(),
+        next_id: 0,
+    }));
+
+    for stream in listener.incoming() {
+        let stream = stream?;
+        let lobby = Arc::clone(&lobby);
+        thread::spawn(move || {
+            let _ = handle_client(stream, lobby);
+        });
+    }
+
+    Ok(())
+}
+
+// the messages as one block of lines, written in a single call so they don't trickle out in pieces
+fn lines(messages: &[ServerMessage]) -> String {
+    messages.iter().map(|message| message.to_line() + "\n").collect()
+}
+
+fn send(connection: &Connection, message: &ServerMessage) -> io::Result<()> {
+    connection.lock().unwrap().write_all(lines(std::slice::from_ref(message)).as_bytes())
+}
+
+// writes the messages to everyone in the list with the lobby unlocked, so one stalled player can't
+// hold up the whole server. anyone whose write fails is dropped from the room and hung up on, which
+// ends their connection thread and tells the others they left.
+fn deliver(lobby: &Mutex<Lobby>, room: &str, recipients: Vec<(usize, Connection)>, messages: &[ServerMessage]) {
+    let text = lines(messages);
+    let mut failed = Vec::new();
+    for (id, stream) in recipients {
+        let mut stream = stream.lock().unwrap();
+        if stream.write_all(text.as_bytes()).is_err() {
+            let _ = stream.shutdown(Shutdown::Both);
+            failed.push(id);
+        }
+    }
+
+    if !failed.is_empty() {
+        let mut lobby = lobby.lock().unwrap();
+        if let Some(entry) = lobby.rooms.get_mut(room) {
+            entry.players.retain(|player| !failed.contains(&player.id));
+            if entry.players.is_empty() {
+                lobby.rooms.remove(room);
+            }
+        }
+    }
+}
+
+fn handle_client(stream: TcpStream, lobby: Arc<Mutex<Lobby>>) -> io::Result<()> {
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+    let connection: Connection = Arc::new(Mutex::new(stream.try_clone()?));
+    // the settings never change once the server is up
+    let settings = lobby.lock().unwrap().settings;
+    let mut joined: Option<(String, usize, String)> = None;
+
+    for line in BufReader::new(stream).lines() {
+        let message = match ClientMessage::parse(&line?) {
+            Ok(message) => message,
+            Err(e) => {
+                send(&connection, &ServerMessage::Error(e))?;
+                continue;
+            }
+        };
+
+        match (message, &joined) {
+            (ClientMessage::Join { room, name }, None) => {
+                let (id, recipients, mut own) = {
+                    let mut lobby = lobby.lock().unwrap();
+                    let lobby = &mut *lobby;
+                    let id = lobby.next_id;
+                    lobby.next_id += 1;
+
+                    let rng = &mut lobby.rng;
+                    let entry = lobby.rooms.entry(room.clone()).or_insert_with(|| Room {
+                        game: Game::new(settings, rng),
+                        players: Vec::new(),
+                    });
+                    let recipients = entry.recipients();
+                    entry.players.push(Player {
+                        id,
+                        stream: Arc::clone(&connection),
+                    });
+                    // taken before the lobby is unlocked, so nothing broadcast to the room can
+                    // reach the new player ahead of their welcome
+                    (id, recipients, connection.lock().unwrap())
+                };
+
+                let welcome = ServerMessage::Welcome {
+                    room: room.clone(),
+                    low: settings.low,
+                    high: settings.high,
+                };
+                own.write_all(lines(&[welcome]).as_bytes())?;
+                drop(own);
+                deliver(&lobby, &room, recipients, &[ServerMessage::Joined { name: name.clone() }]);
+                joined = Some((room, id, name));
+            }
+            (ClientMessage::Join { .. }, Some(_)) => {
+                send(&connection, &ServerMessage::Error(String::from("already in a room")))?;
+            }
+            (ClientMessage::Guess(_), None) => {
+                send(&connection, &ServerMessage::Error(String::from("join a room first")))?;
+            }
+            (ClientMessage::Guess(text), Some((room, _, name))) => {
+                let guess = match input::parse_guess(&text, &settings) {
+                    Ok(guess) => guess,
+                    Err(invalid) => {
+                        send(&connection, &ServerMessage::Error(invalid.to_string()))?;
+                        continue;
+                    }
+                };
+
+                let (recipients, messages) = {
+                    let mut lobby = lobby.lock().unwrap();
+                    let lobby = &mut *lobby;
+                    let Some(entry) = lobby.rooms.get_mut(room) else {
+                        continue;
+                    };
+                    let Outcome::Guess(ordering) = entry.game.submit(guess) else {
+                        continue;
+                    };
+
+                    let mut messages = vec![ServerMessage::Guess {
+                        name: name.clone(),
+                        guess,
+                        ordering,
+                    }];
+                    if ordering == Ordering::Equal {
+                        messages.push(ServerMessage::Winner {
+                            name: name.clone(),
+                            secret: entry.game.secret(),
+                            attempts: entry.game.attempts(),
+                        });
+                        entry.game = Game::new(settings, &mut lobby.rng);
+                        messages.push(ServerMessage::NewRound {
+                            low: settings.low,
+                            high: settings.high,
+                        });
+                    }
+                    (entry.recipients(), messages)
+                };
+
+                deliver(&lobby, room, recipients, &messages);
+            }
+            (ClientMessage::Quit, _) => break,
+        }
+    }
+
+    // leaving, whether by QUIT or by hanging up
+    if let Some((room, id, name)) = joined {
+        let recipients = {
+            let mut lobby = lobby.lock().unwrap();
+            match lobby.rooms.get_mut(&room) {
+                Some(entry) => {
+                    entry.players.retain(|player| player.id != id);
+                    let recipients = entry.recipients();
+                    if entry.players.is_empty() {
+                        lobby.rooms.remove(&room);
+                    }
+                    recipients
+                }
+                None => Vec::new(),
+            }
+        };
+        deliver(&lobby, &room, recipients, &[ServerMessage::Left { name }]);
+    }
+
+    Ok(())
+}
+
+// the client side: joins a room, then forwards the player's lines as guesses
+// while a second thread prints everything the server broadcasts
+pub fn run_client<R: BufRead, W: Write + Send + 'static>(
+    stream: TcpStream,
+    mut input: R,
+    mut output: W,
+    room: &str,
+    name: &str,
+) -> io::Result<()> {
+    let mut writer = stream.try_clone()?;
+    writeln!(writer, "{}", ClientMessage::Join {
+        room: room.to_string(),
+        name: name.to_string(),
+    })?;
+
+    let printer = thread::spawn(move || -> io::Result<()> {
+        for line in BufReader::new(stream).lines() {
+            let line = line?;
+            match ServerMessage::parse(&line) {
+                Ok(message) => writeln!(output, "{}", message)?,
+                Err(_) => writeln!(output, "{}", line)?,
+            }
+            output.flush()?;
+        }
+        Ok(())
+    });
+
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if input.read_line(&mut line)? == 0 || line.trim() == ":quit" {
+            break;
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+        writeln!(writer, "{}", ClientMessage::Guess(line.trim().to_string()))?;
+    }
+
+    writeln!(writer, "{}", ClientMessage::Quit)?;
+    writer.shutdown(std::net::Shutdown::Write)?;
+    printer.join().unwrap_or(Ok(()))
+}
@@ -0,0 +1,122 @@
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use crate::difficulty::{Difficulty, Settings};
+use crate::game::{optimum, Game, Status};
+use crate::storage;
+
+// where every player starts. it's chosen so that a new player's first adaptive game is the normal one.
+pub const START: f64 = 1200.0;
+
+// how far one game can move a rating, as in chess
+const K: f64 = 32.0;
+
+// the normal game, 1..=100 with 10 attempts, is rated 1000
+const BASE: f64 = 1000.0;
+const BASE_BITS: f64 = 6.643856189774724; // log2(100)
+const BASE_SLACK: f64 = 3.0;
+
+// adaptive games are rated this far below the player, so they are expected to score about 0.76
+const HANDICAP: f64 = 200.0;
+
+// the range never gets smaller than 1..=8 or bigger than 1..=2^60
+const MIN_BITS: f64 = 3.0;
+const MAX_BITS: f64 = 60.0;
+
+// how hard a game is on the same scale as the players. doubling the range adds 100 points, and so
+// does each attempt taken off the limit.
+pub fn game_rating(settings: &Settings) -> f64 {
+    let size = settings.size();
+    let bits = (size as f64).log2();
+    // unlimited attempts are as forgiving as ten spare ones
+    let slack = match settings.max_attempts {
+        Some(max) => (max as f64 - optimum(size) as f64).min(10.0),
+        None => 10.0,
+    };
+    BASE + 100.0 * (bits - BASE_BITS) + 100.0 * (BASE_SLACK - slack)
+}
+
+// the game an adaptive player gets next: a little below their rating, made harder first by taking
+// spare attempts away and then by widening the range
+pub fn settings_for(rating: f64) -> Settings {
+    let target = rating - HANDICAP;
+    let slack = (BASE_SLACK - ((target - BASE) / 300.0).round()).clamp(0.0, 6.0);
+    let bits = (BASE_BITS + (target - BASE - 100.0 * (BASE_SLACK - slack)) / 100.0).clamp(MIN_BITS, MAX_BITS);
+
+    let high = bits.exp2().round() as i128;
+    let max_attempts = optimum(high as u128) + slack as u32;
+    Settings::new(Difficulty::Custom, 1, high, Some(max_attempts))
+}
+
+// how well a finished game went, from 0 to 1: binary search's worst case over the attempts taken,
+// so a win in optimum(range) guesses or fewer scores 1, and losing or giving up scores 0
+pub fn score(game: &Game) -> f64 {
+    match game.status() {
+        Status::Won => {
+            let optimum = optimum(game.settings().size()) as f64;
+            (optimum / game.attempts().max(1) as f64).min(1.0)
+        }
+        _ => 0.0,
+    }
+}
+
+// the score a player with this rating is expected to get from a game rated game_rating
+pub fn expected(rating: f64, game_rating: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((game_rating - rating) / 400.0))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Rating {
+    pub rating: f64,
+    pub games: u32,
+}
+
+impl Default for Rating {
+    fn default() -> Rating {
+        Rating { rating: START, games: 0 }
+    }
+}
+
+impl Rating {
+    // moves the rating towards how the game actually went
+    pub fn update(&mut self, game: &Game) {
+        let expected = expected(self.rating, game_rating(game.settings()));
+        self.rating += K * (score(game) - expected);
+        self.games += 1;
+    }
+}
+
+// every player's rating, kept as a JSON file next to the leaderboard
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Ratings {
+    players: BTreeMap<String, Rating>,
+}
+
+impl Ratings {
+    pub fn default_path() -> PathBuf {
+        storage::data_dir().join("ratings.json")
+    }
+
+    // a missing file means nobody has a rating yet
+    pub fn load(path: &Path) -> io::Result<Ratings> {
+        storage::load(path)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        storage::save(path, self)
+    }
+
+    // players who haven't finished a game yet get the starting rating
+    pub fn get(&self, player: &str) -> Rating {
+        self.players.get(player).copied().unwrap_or_default()
+    }
+
+    // returns the rating before and after the game
+    pub fn record(&mut self, player: &str, game: &Game) -> (Rating, Rating) {
+        let rating = self.players.entry(player.to_string()).or_default();
+        let before = *rating;
+        rating.update(game);
+        (before, *rating)
+    }
+}
@@ -0,0 +1,212 @@
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+use crate::difficulty::Settings;
+use crate::locale::Locale;
+
+// the player's reply to "Is it N?"
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Answer {
+    // the player's number is bigger than the guess
+    Higher,
+    // the player's number is smaller than the guess
+    Lower,
+    Correct,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Higher => f.write_str("higher"),
+            Answer::Lower => f.write_str("lower"),
+            Answer::Correct => f.write_str("correct"),
+        }
+    }
+}
+
+impl FromStr for Answer {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Answer, String> {
+        match s.trim().to_lowercase().as_str() {
+            "h" | "higher" | "bigger" | "+" => Ok(Answer::Higher),
+            "l" | "lower" | "smaller" | "-" => Ok(Answer::Lower),
+            "c" | "correct" | "yes" | "y" | "=" => Ok(Answer::Correct),
+            other => Err(Locale::En.format("reverse_not_an_answer", &[("text", &other)])),
+        }
+    }
+}
+
+// an answer that leaves no number the player could be thinking of
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Contradiction {
+    pub guess: i128,
+    pub answer: Answer,
+    // the earlier answer it cannot be reconciled with, as (answer number, guess, answer).
+    // None means the answer contradicts the range itself.
+    pub conflict: Option<(usize, i128, Answer)>,
+}
+
+impl Contradiction {
+    pub fn message(&self, locale: Locale) -> String {
+        match self.conflict {
+            // only higher and lower answers move the bounds, so only they can be contradicted
+            Some((number, guess, answer)) => locale.format(
+                if answer == Answer::Higher { "reverse_conflict_higher" } else { "reverse_conflict_lower" },
+                &[("answer", &self.answer), ("guess", &self.guess), ("number", &number), ("earlier", &guess)],
+            ),
+            None => locale.format("reverse_outside_range", &[("answer", &self.answer), ("guess", &self.guess)]),
+        }
+    }
+}
+
+impl fmt::Display for Contradiction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message(Locale::En))
+    }
+}
+
+// the computer's side of the reverse game: a binary search that remembers every answer
+#[derive(Debug, Clone)]
+pub struct Guesser {
+    low: i128,
+    high: i128,
+    // indexes into answers for the answers that set low and high, if any
+    low_source: Option<usize>,
+    high_source: Option<usize>,
+    answers: Vec<(i128, Answer)>,
+    found: Option<i128>,
+}
+
+impl Guesser {
+    pub fn new(settings: &Settings) -> Guesser {
+        Guesser {
+            low: settings.low,
+            high: settings.high,
+            low_source: None,
+            high_source: None,
+            answers: Vec::new(),
+            found: None,
+        }
+    }
+
+    // the middle of everything still possible
+    pub fn next_guess(&self) -> i128 {
+        midpoint(self.low, self.high)
+    }
+
+    // records an answer to a guess, unless it contradicts an earlier one
+    pub fn answer(&mut self, guess: i128, answer: Answer) -> Result<(), Contradiction> {
+        let contradiction = |source: Option<usize>, answers: &[(i128, Answer)]| Contradiction {
+            guess,
+            answer,
+            conflict: source.map(|i| (i + 1, answers[i].0, answers[i].1)),
+        };
+
+        match answer {
+            Answer::Higher if guess >= self.high => {
+                return Err(contradiction(self.high_source, &self.answers))
+            }
+            Answer::Lower if guess <= self.low => {
+                return Err(contradiction(self.low_source, &self.answers))
+            }
+            Answer::Correct if guess < self.low => {
+                return Err(contradiction(self.low_source, &self.answers))
+            }
+            Answer::Correct if guess > self.high => {
+                return Err(contradiction(self.high_source, &self.answers))
+            }
+            _ => {}
+        }
+
+        self.answers.push((guess, answer));
+        let index = self.answers.len() - 1;
+        match answer {
+            Answer::Higher => {
+                self.low = guess + 1;
+                self.low_source = Some(index);
+            }
+            Answer::Lower => {
+                self.high = guess - 1;
+                self.high_source = Some(index);
+            }
+            Answer::Correct => self.found = Some(guess),
+        }
+
+        Ok(())
+    }
+
+    pub fn answers(&self) -> &[(i128, Answer)] {
+        &self.answers
+    }
+
+    pub fn found(&self) -> Option<i128> {
+        self.found
+    }
+}
+
+// the floor of (low + high) / 2 without overflowing, even across all of i128
+pub fn midpoint(low: i128, high: i128) -> i128 {
+    (low >> 1) + (high >> 1) + (low & high & 1)
+}
+
+// plays the reverse game: the player picks a number and the computer guesses it.
+// returns how many guesses it took, or None if the player left first.
+pub fn reverse_game<R: BufRead, W: Write>(
+    mut input: R,
+    mut output: W,
+    settings: Settings,
+    locale: Locale,
+) -> io::Result<Option<u32>> {
+    writeln!(
+        output,
+        "{}",
+        locale.format("reverse_think", &[("low", &settings.low), ("high", &settings.high)])
+    )?;
+    writeln!(output, "{}", locale.text("reverse_instructions"))?;
+
+    let mut guesser = Guesser::new(&settings);
+    let mut guesses = 0;
+
+    let number = loop {
+        if let Some(number) = guesser.found() {
+            break number;
+        }
+
+        let guess = guesser.next_guess();
+        guesses += 1;
+        writeln!(output, "{}", locale.format("reverse_is_it", &[("guess", &guess)]))?;
+
+        loop {
+            let mut line = String::new();
+            if input.read_line(&mut line)? == 0 || line.trim().eq_ignore_ascii_case(":quit") {
+                writeln!(output, "{}", locale.text("goodbye"))?;
+                return Ok(None);
+            }
+
+            let answer: Answer = match line.parse() {
+                Ok(answer) => answer,
+                Err(_) => {
+                    let text = line.trim().to_lowercase();
+                    writeln!(output, "{}", locale.format("reverse_not_an_answer", &[("text", &text)]))?;
+                    continue;
+                }
+            };
+
+            match guesser.answer(guess, answer) {
+                Ok(()) => break,
+                Err(contradiction) => {
+                    writeln!(output, "{}", contradiction.message(locale))?;
+                    writeln!(output, "{}", locale.format("reverse_is_it", &[("guess", &guess)]))?;
+                }
+            }
+        }
+    };
+
+    writeln!(
+        output,
+        "{}",
+        locale.format("reverse_got_it", &[("number", &number), ("guesses", &guesses)])
+    )?;
+    Ok(Some(guesses))
+}
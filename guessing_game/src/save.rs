@@ -0,0 +1,109 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use crate::difficulty::Settings;
+use crate::game::Game;
+use crate::storage;
+
+// bumped whenever the saved fields change, so old saves are refused rather than misread
+const VERSION: u32 = 1;
+
+// mixed into the checksum so that editing a save by hand, even with the checksum recomputed
+// by a plain FNV tool, is caught. it only keeps honest players honest: the key ships in the binary.
+const KEY: &[u8] = b"guessing_game save v1";
+
+// everything needed to pick a game up where it was left
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedGame {
+    pub seed: u64,
+    pub settings: Settings,
+    pub secret: i128,
+    // every counted guess in order; replaying them rebuilds the rest of the game
+    pub guesses: Vec<i128>,
+    pub elapsed_ms: u64,
+}
+
+// what is actually written: the game and a checksum of it
+#[derive(Serialize, Deserialize)]
+struct SaveFile {
+    version: u32,
+    game: SavedGame,
+    checksum: String,
+}
+
+impl SavedGame {
+    pub fn default_path() -> PathBuf {
+        storage::data_dir().join("save.json")
+    }
+
+    pub fn from_game(game: &Game, seed: u64, elapsed: Duration) -> SavedGame {
+        SavedGame {
+            seed,
+            settings: *game.settings(),
+            secret: game.secret(),
+            guesses: game.history().iter().map(|&(guess, _)| guess).collect(),
+            elapsed_ms: elapsed.as_millis() as u64,
+        }
+    }
+
+    // replays the saved guesses onto a fresh game with the same secret
+    pub fn restore(&self) -> Game {
+        let mut game = Game::with_secret(self.settings, self.secret);
+        for &guess in &self.guesses {
+            game.submit(guess);
+        }
+        game
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_millis(self.elapsed_ms)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let file = SaveFile {
+            version: VERSION,
+            game: self.clone(),
+            checksum: checksum(self)?,
+        };
+        let contents = serde_json::to_string_pretty(&file).map_err(invalid_data)?;
+        fs::write(path, contents)
+    }
+
+    // refuses a save from another version, one that was edited, or one that makes no sense
+    pub fn load(path: &Path) -> io::Result<SavedGame> {
+        let contents = fs::read_to_string(path)?;
+        let file: SaveFile = serde_json::from_str(&contents).map_err(invalid_data)?;
+
+        if file.version != VERSION {
+            return Err(invalid_data(format!("save version {} is not supported", file.version)));
+        }
+        if file.checksum != checksum(&file.game)? {
+            return Err(invalid_data("the save has been tampered with"));
+        }
+
+        let game = file.game;
+        game.settings.validate().map_err(invalid_data)?;
+        if !game.settings.contains(game.secret) {
+            return Err(invalid_data("the saved secret is outside the saved range"));
+        }
+        if game.restore().is_finished() {
+            return Err(invalid_data("the saved game is already over"));
+        }
+        Ok(game)
+    }
+}
+
+fn invalid_data<E: Into<Box<dyn std::error::Error + Send + Sync>>>(error: E) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+// FNV-1a over the key and the game's JSON, as hex
+fn checksum(game: &SavedGame) -> io::Result<String> {
+    let json = serde_json::to_vec(game).map_err(invalid_data)?;
+    Ok(format!("{:016x}", storage::fnv1a(&[KEY, &json])))
+}
@@ -0,0 +1,138 @@
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use crate::difficulty::Settings;
+use crate::game::{Game, Status};
+use crate::strategy::{self, Strategy};
+
+// no game goes on longer than this. binary search never needs more than 128 guesses, but linear
+// and bad could otherwise take as long as the range is wide.
+pub const MAX_ATTEMPTS: u32 = 10_000;
+
+// how many attempts a strategy needed over a batch of games
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub name: &'static str,
+    pub games: usize,
+    pub mean: f64,
+    pub median: f64,
+    pub min: u32,
+    pub max: u32,
+    // attempts -> number of games that took that many
+    pub histogram: BTreeMap<u32, usize>,
+    // games stopped at MAX_ATTEMPTS without finding the secret; they count as MAX_ATTEMPTS above
+    pub unfinished: usize,
+}
+
+impl Stats {
+    pub fn from_attempts(name: &'static str, mut attempts: Vec<u32>) -> Stats {
+        attempts.sort_unstable();
+
+        let games = attempts.len();
+        let mut histogram = BTreeMap::new();
+        for &count in &attempts {
+            *histogram.entry(count).or_insert(0) += 1;
+        }
+
+        let mean = if games == 0 {
+            0.0
+        } else {
+            attempts.iter().map(|&a| a as f64).sum::<f64>() / games as f64
+        };
+        let median = match games {
+            0 => 0.0,
+            n if n % 2 == 1 => attempts[n / 2] as f64,
+            n => (attempts[n / 2 - 1] as f64 + attempts[n / 2] as f64) / 2.0,
+        };
+
+        Stats {
+            name,
+            games,
+            mean,
+            median,
+            min: attempts.first().copied().unwrap_or(0),
+            max: attempts.last().copied().unwrap_or(0),
+            histogram,
+            unfinished: 0,
+        }
+    }
+
+    pub fn print<W: Write>(&self, output: &mut W) -> io::Result<()> {
+        // the longest bar is this many characters wide
+        const WIDTH: usize = 40;
+        // wider spreads are grouped so the histogram stays readable
+        const MAX_ROWS: u32 = 20;
+
+        writeln!(
+            output,
+            "{}: {} games, mean {:.2}, median {:.1}, min {}, max {}",
+            self.name, self.games, self.mean, self.median, self.min, self.max
+        )?;
+        if self.unfinished > 0 {
+            writeln!(output, "  {} games were stopped after {} attempts", self.unfinished, MAX_ATTEMPTS)?;
+        }
+
+        let bucket = (self.max - self.min + 1).div_ceil(MAX_ROWS).max(1);
+        let mut rows: BTreeMap<u32, usize> = BTreeMap::new();
+        for (&attempts, &count) in &self.histogram {
+            *rows.entry((attempts - self.min) / bucket).or_insert(0) += count;
+        }
+
+        let tallest = rows.values().copied().max().unwrap_or(0);
+        for (row, count) in rows {
+            let start = self.min + row * bucket;
+            let label = if bucket == 1 {
+                start.to_string()
+            } else {
+                format!("{}-{}", start, start + bucket - 1)
+            };
+            let bar = (count * WIDTH).div_ceil(tallest.max(1));
+            writeln!(output, "  {:>9} | {:<width$} {}", label, "#".repeat(bar), count, width = WIDTH)?;
+        }
+
+        Ok(())
+    }
+}
+
+// plays the same games with every strategy so the numbers are comparable.
+// the secrets and each strategy's own randomness both come from the seed, so a run can be repeated exactly.
+pub fn simulate(
+    strategies: &mut [Box<dyn Strategy>],
+    settings: Settings,
+    games: usize,
+    seed: u64,
+) -> Vec<Stats> {
+    // every strategy gets the same generous limit in place of the game's own, so a slow one still ends
+    let settings = Settings {
+        max_attempts: Some(MAX_ATTEMPTS),
+        ..settings
+    };
+
+    let mut secrets_rng = StdRng::seed_from_u64(seed);
+    let secrets: Vec<i128> = (0..games)
+        .map(|_| secrets_rng.gen_range(settings.low..=settings.high))
+        .collect();
+
+    strategies
+        .iter_mut()
+        .map(|strategy| {
+            let mut rng = StdRng::seed_from_u64(seed.wrapping_add(1));
+            let mut unfinished = 0;
+            let attempts = secrets
+                .iter()
+                .map(|&secret| {
+                    let mut game = Game::with_secret(settings, secret);
+                    if strategy::play(strategy.as_mut(), &mut game, &mut rng) != Status::Won {
+                        unfinished += 1;
+                    }
+                    game.attempts()
+                })
+                .collect();
+            Stats {
+                unfinished,
+                ..Stats::from_attempts(strategy.name(), attempts)
+            }
+        })
+        .collect()
+}
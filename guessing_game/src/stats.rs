@@ -0,0 +1,164 @@
+use std::io::{self, Write};
+use crate::history::{Ending, GameRecord};
+use crate::storage;
+
+// how far the recent half of a player's wins must differ from the earlier half to count as a trend
+const TREND_THRESHOLD: f64 = 0.5;
+
+// the weeks shown in the trend table, most recent last
+const WEEKS_SHOWN: usize = 8;
+
+// one row of the trend table
+#[derive(Debug, Clone, PartialEq)]
+pub struct Week {
+    // the monday the week starts on, as YYYY-MM-DD
+    pub start: String,
+    pub games: usize,
+    pub wins: usize,
+    // extra guesses over binary search, averaged over the week's wins
+    pub extra: Option<f64>,
+}
+
+// a player's totals, worked out from their history
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayerStats {
+    pub player: String,
+    pub games: usize,
+    pub wins: usize,
+    pub average_attempts: Option<f64>,
+    // the win with the fewest attempts
+    pub best: Option<(u32, i128, i128)>,
+    // what binary search averages on the same secrets, and how many more guesses the player took
+    pub optimal_average: Option<f64>,
+    pub extra: Option<f64>,
+    pub weeks: Vec<Week>,
+    // recent wins' extra guesses minus earlier ones'; below zero is an improvement
+    pub trend: Option<f64>,
+}
+
+fn average(values: impl Iterator<Item = f64>) -> Option<f64> {
+    let (sum, count) = values.fold((0.0, 0), |(sum, count), value| (sum + value, count + 1));
+    if count == 0 {
+        None
+    } else {
+        Some(sum / count as f64)
+    }
+}
+
+fn extra(game: &GameRecord) -> f64 {
+    game.attempts() as f64 - game.optimal as f64
+}
+
+// the day number of the monday on or before a timestamp. 1970-01-01 was a thursday, so the
+// days before the first monday have no monday to go back to and start at day 0 instead.
+fn week_start(timestamp: u64) -> u64 {
+    let day = timestamp / 86_400;
+    day.saturating_sub((day + 3) % 7)
+}
+
+impl PlayerStats {
+    // games must be oldest first, as History::games_of gives them
+    pub fn from_games(player: &str, games: &[&GameRecord]) -> PlayerStats {
+        let wins: Vec<&GameRecord> = games.iter().copied().filter(|game| game.ending == Ending::Won).collect();
+
+        let mut weeks: Vec<(u64, Vec<&GameRecord>)> = Vec::new();
+        for &game in games {
+            let start = week_start(game.timestamp);
+            match weeks.last_mut() {
+                Some((week, members)) if *week == start => members.push(game),
+                _ => weeks.push((start, vec![game])),
+            }
+        }
+        let skip = weeks.len().saturating_sub(WEEKS_SHOWN);
+        let weeks = weeks
+            .into_iter()
+            .skip(skip)
+            .map(|(start, members)| Week {
+                start: storage::format_date(start * 86_400),
+                games: members.len(),
+                wins: members.iter().filter(|game| game.ending == Ending::Won).count(),
+                extra: average(members.iter().filter(|game| game.ending == Ending::Won).map(|game| extra(game))),
+            })
+            .collect();
+
+        // with fewer than four wins either half is too small to say anything
+        let trend = if wins.len() >= 4 {
+            let (earlier, recent) = wins.split_at(wins.len() / 2);
+            let earlier = average(earlier.iter().map(|game| extra(game)));
+            let recent = average(recent.iter().map(|game| extra(game)));
+            earlier.zip(recent).map(|(earlier, recent)| recent - earlier)
+        } else {
+            None
+        };
+
+        PlayerStats {
+            player: player.to_string(),
+            games: games.len(),
+            wins: wins.len(),
+            average_attempts: average(wins.iter().map(|game| game.attempts() as f64)),
+            best: wins
+                .iter()
+                .min_by_key(|game| (game.attempts(), game.timestamp))
+                .map(|game| (game.attempts(), game.low, game.high)),
+            optimal_average: average(wins.iter().map(|game| game.optimal as f64)),
+            extra: average(wins.iter().map(|game| extra(game))),
+            weeks,
+            trend,
+        }
+    }
+
+    pub fn print<W: Write>(&self, output: &mut W) -> io::Result<()> {
+        writeln!(output, "Stats for {}:", self.player)?;
+        writeln!(output, "  games played      {}", self.games)?;
+        writeln!(
+            output,
+            "  wins              {} ({:.0}%)",
+            self.wins,
+            100.0 * self.wins as f64 / self.games.max(1) as f64
+        )?;
+        if let Some(average) = self.average_attempts {
+            writeln!(output, "  average attempts  {:.1} per win", average)?;
+        }
+        if let Some((attempts, low, high)) = self.best {
+            let plural = if attempts == 1 { "" } else { "s" };
+            writeln!(output, "  best              {} attempt{} on {}..={}", attempts, plural, low, high)?;
+        }
+        if let (Some(optimal), Some(extra)) = (self.optimal_average, self.extra) {
+            // a lucky guess can beat binary search, so the difference goes either way
+            let (amount, direction) = if extra >= 0.0 { (extra, "more") } else { (-extra, "fewer") };
+            writeln!(
+                output,
+                "  vs binary search  {:.1} per win on the same secrets; you took {:.1} {}",
+                optimal,
+                amount,
+                direction
+            )?;
+        }
+
+        if self.weeks.len() > 1 {
+            writeln!(output, "  by week:")?;
+            for week in &self.weeks {
+                write!(output, "    {}  {:>3} games  {:>3} won", week.start, week.games, week.wins)?;
+                match week.extra {
+                    Some(extra) => writeln!(output, "  {:+.1} guesses vs binary search", extra)?,
+                    None => writeln!(output)?,
+                }
+            }
+        }
+
+        match self.trend {
+            Some(change) if change <= -TREND_THRESHOLD => writeln!(
+                output,
+                "  trend             improving: recent wins take {:.1} fewer guesses",
+                -change
+            ),
+            Some(change) if change >= TREND_THRESHOLD => writeln!(
+                output,
+                "  trend             slipping: recent wins take {:.1} more guesses",
+                change
+            ),
+            Some(_) => writeln!(output, "  trend             steady"),
+            None => Ok(()),
+        }
+    }
+}
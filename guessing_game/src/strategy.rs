@@ -0,0 +1,113 @@
+use rand::{Rng, RngCore};
+use crate::game::{Game, Status};
+use crate::reverse::midpoint;
+
+// a way of picking guesses, so guess the number can be played without a person
+pub trait Strategy {
+    fn name(&self) -> &'static str;
+
+    // the next guess, given everything the game has answered so far
+    fn next_guess(&mut self, game: &Game, rng: &mut dyn RngCore) -> i128;
+}
+
+// always guesses the middle of the remaining interval
+pub struct BinarySearch;
+
+impl Strategy for BinarySearch {
+    fn name(&self) -> &'static str {
+        "binary"
+    }
+
+    fn next_guess(&mut self, game: &Game, _rng: &mut dyn RngCore) -> i128 {
+        let (low, high) = game.remaining();
+        midpoint(low, high)
+    }
+}
+
+// guesses anywhere in the remaining interval
+pub struct RandomGuess;
+
+impl Strategy for RandomGuess {
+    fn name(&self) -> &'static str {
+        "random"
+    }
+
+    fn next_guess(&mut self, game: &Game, rng: &mut dyn RngCore) -> i128 {
+        let (low, high) = game.remaining();
+        rng.gen_range(low..=high)
+    }
+}
+
+// counts up from the bottom of the remaining interval
+pub struct Linear;
+
+impl Strategy for Linear {
+    fn name(&self) -> &'static str {
+        "linear"
+    }
+
+    fn next_guess(&mut self, game: &Game, _rng: &mut dyn RngCore) -> i128 {
+        game.remaining().0
+    }
+}
+
+// splits the remaining interval at the golden ratio instead of the middle
+pub struct GoldenSection;
+
+impl Strategy for GoldenSection {
+    fn name(&self) -> &'static str {
+        "golden"
+    }
+
+    fn next_guess(&mut self, game: &Game, _rng: &mut dyn RngCore) -> i128 {
+        // 1 - 1/phi, so the smaller piece is on the low side
+        const RATIO: f64 = 0.381_966_011_250_105;
+
+        let (low, high) = game.remaining();
+        // the width as a float straight from abs_diff, since far from zero the bounds' own floats lose it
+        let offset = (high.abs_diff(low) as f64 * RATIO) as i128;
+        low.saturating_add(offset).clamp(low, high)
+    }
+}
+
+// ignores every answer and guesses anywhere in the original range, repeats included
+pub struct Bad;
+
+impl Strategy for Bad {
+    fn name(&self) -> &'static str {
+        "bad"
+    }
+
+    fn next_guess(&mut self, game: &Game, rng: &mut dyn RngCore) -> i128 {
+        let settings = game.settings();
+        rng.gen_range(settings.low..=settings.high)
+    }
+}
+
+// one of every strategy, in the order they are reported
+pub fn all() -> Vec<Box<dyn Strategy>> {
+    vec![
+        Box::new(BinarySearch),
+        Box::new(GoldenSection),
+        Box::new(RandomGuess),
+        Box::new(Linear),
+        Box::new(Bad),
+    ]
+}
+
+pub fn by_name(name: &str) -> Result<Box<dyn Strategy>, String> {
+    all()
+        .into_iter()
+        .find(|strategy| strategy.name() == name.trim().to_lowercase())
+        .ok_or_else(|| format!("unknown strategy: {}", name))
+}
+
+// lets a strategy play a game to the end without any input or output
+pub fn play<S: Strategy + ?Sized>(strategy: &mut S, game: &mut Game, rng: &mut dyn RngCore) -> Status {
+    while !game.is_finished() {
+        let settings = game.settings();
+        let guess = strategy.next_guess(game, rng).clamp(settings.low, settings.high);
+        game.submit(guess);
+    }
+    game.status()
+}
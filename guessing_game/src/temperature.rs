@@ -0,0 +1,105 @@
+use std::fmt;
+use crate::game::Game;
+use crate::locale::Locale;
+
+// how close a guess is to the secret, relative to the size of the range
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Temperature {
+    Burning,
+    Hot,
+    Warm,
+    Cold,
+    Freezing,
+}
+
+impl Temperature {
+    // the thresholds are fractions of the range, so "hot" means the same on 1..=10 as on 1..=1000000
+    pub fn of(distance: u128, span: u128) -> Temperature {
+        let fraction = distance as f64 / span.max(1) as f64;
+
+        if distance <= 1 || fraction <= 0.02 {
+            Temperature::Burning
+        } else if fraction <= 0.05 {
+            Temperature::Hot
+        } else if fraction <= 0.15 {
+            Temperature::Warm
+        } else if fraction <= 0.35 {
+            Temperature::Cold
+        } else {
+            Temperature::Freezing
+        }
+    }
+}
+
+impl Temperature {
+    pub fn message(self, locale: Locale) -> &'static str {
+        match self {
+            Temperature::Burning => locale.text("temperature_burning"),
+            Temperature::Hot => locale.text("temperature_hot"),
+            Temperature::Warm => locale.text("temperature_warm"),
+            Temperature::Cold => locale.text("temperature_cold"),
+            Temperature::Freezing => locale.text("temperature_freezing"),
+        }
+    }
+}
+
+impl fmt::Display for Temperature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.message(Locale::En))
+    }
+}
+
+// whether a guess got closer to the secret than the one before it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trend {
+    Warmer,
+    Colder,
+    Same,
+}
+
+impl Trend {
+    pub fn between(previous_distance: u128, distance: u128) -> Trend {
+        match distance.cmp(&previous_distance) {
+            std::cmp::Ordering::Less => Trend::Warmer,
+            std::cmp::Ordering::Greater => Trend::Colder,
+            std::cmp::Ordering::Equal => Trend::Same,
+        }
+    }
+}
+
+impl Trend {
+    pub fn message(self, locale: Locale) -> &'static str {
+        match self {
+            Trend::Warmer => locale.text("trend_warmer"),
+            Trend::Colder => locale.text("trend_colder"),
+            Trend::Same => locale.text("trend_same"),
+        }
+    }
+}
+
+impl fmt::Display for Trend {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.message(Locale::En))
+    }
+}
+
+// the temperature line for the game's latest guess, or None before the first guess or after a win
+pub fn feedback(game: &Game, locale: Locale) -> Option<String> {
+    let history = game.history();
+    let &(guess, _) = history.last()?;
+    if guess == game.secret() {
+        return None;
+    }
+
+    let span = game.settings().size();
+    let distance = guess.abs_diff(game.secret());
+    let temperature = Temperature::of(distance, span);
+
+    match history.len().checked_sub(2).map(|i| history[i].0) {
+        Some(previous) => {
+            let trend = Trend::between(previous.abs_diff(game.secret()), distance);
+            Some(format!("{} {}", temperature.message(locale), trend.message(locale)))
+        }
+        None => Some(temperature.message(locale).to_string()),
+    }
+}
@@ -0,0 +1,201 @@
+use std::io::{self, BufRead, Write};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::Duration;
+use rand::Rng;
+use crate::clock::Clock;
+use crate::command::{self, Command};
+use crate::difficulty::Settings;
+use crate::game::{Game, Outcome, Status};
+use crate::locale::Locale;
+use crate::{input, run_command};
+
+// the default time budget
+pub const DEFAULT_BUDGET: Duration = Duration::from_secs(30);
+
+// how a time attack went
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Score {
+    // secrets found before time ran out
+    pub solved: u32,
+    // secrets skipped with :giveup or lost to the attempt limit
+    pub missed: u32,
+    // every guess that counted, across all secrets
+    pub guesses: u32,
+    // false if the player left with :quit or the end of input
+    pub finished: bool,
+}
+
+// how often a wait for input looks at the clock again
+const TICK: Duration = Duration::from_millis(50);
+
+fn seconds_left(budget: Duration, elapsed: Duration) -> f64 {
+    budget.saturating_sub(elapsed).as_secs_f64()
+}
+
+// what came of waiting for the player
+enum Wait {
+    Line(String),
+    EndOfInput,
+    TimesUp,
+}
+
+// reads lines on a thread of its own, one each time it is asked for one, so that waiting for
+// a line can give up when the time runs out. nothing is read ahead: a line is only taken once
+// the game is ready for it.
+struct LineReader {
+    requests: Sender<()>,
+    lines: Receiver<io::Result<String>>,
+    // a line was asked for and hasn't arrived yet
+    waiting: bool,
+}
+
+impl LineReader {
+    fn spawn<R: BufRead + Send + 'static>(mut input: R) -> LineReader {
+        let (requests, asked) = mpsc::channel::<()>();
+        let (send, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for () in asked {
+                let mut line = String::new();
+                let read = match input.read_line(&mut line) {
+                    Ok(0) => break,
+                    Ok(_) => Ok(line),
+                    Err(e) => Err(e),
+                };
+                let failed = read.is_err();
+                if send.send(read).is_err() || failed {
+                    break;
+                }
+            }
+        });
+        LineReader {
+            requests,
+            lines,
+            waiting: false,
+        }
+    }
+
+    // waits for the next line until the clock passes the budget
+    fn next<C: Clock>(&mut self, clock: &C, budget: Duration) -> io::Result<Wait> {
+        if !self.waiting {
+            // a reader that has already stopped shows up below as a closed channel
+            let _ = self.requests.send(());
+            self.waiting = true;
+        }
+        loop {
+            let remaining = budget.saturating_sub(clock.elapsed());
+            if remaining.is_zero() {
+                return Ok(Wait::TimesUp);
+            }
+            match self.lines.recv_timeout(remaining.min(TICK)) {
+                Ok(line) => {
+                    self.waiting = false;
+                    return line.map(Wait::Line);
+                }
+                Err(RecvTimeoutError::Timeout) => continue,
+                Err(RecvTimeoutError::Disconnected) => return Ok(Wait::EndOfInput),
+            }
+        }
+    }
+}
+
+// finds as many secrets as possible before the budget runs out, one after another.
+// input is read on another thread so the round ends when the time does, even mid-thought,
+// and the clock is read again when a line arrives, so a guess typed after the buzzer doesn't count.
+pub fn time_attack<R: BufRead + Send + 'static, W: Write, G: Rng, C: Clock>(
+    input: R,
+    mut output: W,
+    settings: Settings,
+    budget: Duration,
+    clock: C,
+    rng: &mut G,
+    locale: Locale,
+) -> io::Result<Score> {
+    let mut score = Score::default();
+
+    let seconds = format!("{:.0}", budget.as_secs_f64());
+    writeln!(output, "{}", locale.format("time_attack_title", &[("seconds", &seconds)]))?;
+    writeln!(
+        output,
+        "{}",
+        locale.format("time_attack_range", &[("low", &settings.low), ("high", &settings.high)])
+    )?;
+
+    let mut game = Game::new(settings, rng);
+    writeln!(output, "{}", locale.format("time_attack_secret", &[("number", &1)]))?;
+
+    let mut reader = LineReader::spawn(input);
+
+    loop {
+        let remaining = format!("{:.1}", seconds_left(budget, clock.elapsed()));
+        writeln!(output, "{}", locale.format("time_attack_prompt", &[("seconds", &remaining)]))?;
+
+        let line = match reader.next(&clock, budget)? {
+            Wait::Line(line) if clock.elapsed() < budget => line,
+            Wait::Line(_) | Wait::TimesUp => {
+                writeln!(output, "{}", locale.format("time_attack_times_up", &[("secret", &game.secret())]))?;
+                score.finished = true;
+                break;
+            }
+            Wait::EndOfInput => {
+                writeln!(output, "{}", locale.text("time_attack_no_more_input"))?;
+                break;
+            }
+        };
+
+        if let Some(command) = command::parse_command(&line) {
+            match command {
+                Ok(Command::Quit) => {
+                    writeln!(output, "{}", locale.text("goodbye"))?;
+                    break;
+                }
+                Ok(command) => run_command(&mut output, &mut game, command, locale)?,
+                Err(unknown) => writeln!(output, "{}", unknown.message(locale))?,
+            }
+        } else {
+            let guess = match input::parse_guess(&line, &settings) {
+                Ok(num) => num,
+                Err(invalid) => {
+                    writeln!(output, "{}", invalid.message(locale))?;
+                    continue;
+                }
+            };
+
+            if let Outcome::Guess(ordering) = game.submit(guess) {
+                score.guesses += 1;
+                writeln!(output, "{}", locale.describe(ordering))?;
+            }
+            if game.status() == Status::Lost {
+                writeln!(output, "{}", locale.format("out_of_attempts", &[("secret", &game.secret())]))?;
+            }
+        }
+
+        // a decided game, won or not, moves straight on to the next secret
+        if game.is_finished() {
+            if game.status() == Status::Won {
+                score.solved += 1;
+            } else {
+                score.missed += 1;
+            }
+            game = Game::new(settings, rng);
+            let number = score.solved + score.missed + 1;
+            writeln!(output, "{}", locale.format("time_attack_secret", &[("number", &number)]))?;
+        }
+    }
+
+    writeln!(output)?;
+    writeln!(
+        output,
+        "{}",
+        locale.format(
+            "time_attack_score",
+            &[("solved", &score.solved), ("missed", &score.missed), ("guesses", &score.guesses)]
+        )
+    )?;
+    if score.solved > 0 {
+        let average = format!("{:.1}", score.guesses as f64 / score.solved as f64);
+        writeln!(output, "{}", locale.format("time_attack_average", &[("average", &average)]))?;
+    }
+
+    Ok(score)
+}
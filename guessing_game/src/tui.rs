@@ -0,0 +1,288 @@
+use std::cmp::Ordering;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+use ratatui::crossterm::event::{self, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Paragraph, Row, Table};
+use ratatui::Frame;
+use crate::command::{self, Command};
+use crate::events::Event;
+use crate::game::{Game, Outcome, Status};
+use crate::leaderboard::{Entry, Leaderboard, Variant, TOP};
+use crate::save::SavedGame;
+use crate::locale::Locale;
+use crate::{input, run_command, temperature, Session, Summary};
+
+// what the screen shows besides the game itself
+struct Screen<'a> {
+    game: &'a mut Game,
+    top: Vec<&'a Entry>,
+    typed: String,
+    // the reply to the last line typed
+    message: String,
+    // the game is over and the board stays up until a key is pressed
+    done: bool,
+    // how the game was left, for the event log
+    reason: &'static str,
+    locale: Locale,
+}
+
+// the same game as play_game, drawn full-screen.
+// raw mode swallows Ctrl-C as a key press, so it saves the game here instead of in a signal handler.
+pub fn play_game(game: &mut Game, session: &Session, leaderboard: &Leaderboard) -> io::Result<Summary> {
+    let settings = *game.settings();
+    let started = Instant::now();
+    let elapsed = |started: Instant| session.elapsed + started.elapsed();
+
+    let mut screen = Screen {
+        top: leaderboard.top(Variant::Number, settings.difficulty, TOP),
+        typed: String::new(),
+        message: if game.attempts() > 0 {
+            session.locale.format("welcome_back", &[("attempts", &game.attempts())])
+        } else {
+            session.locale.text("tui_instructions").to_string()
+        },
+        game,
+        done: false,
+        reason: "quit",
+        locale: session.locale,
+    };
+    session.log(Event::start(screen.game, session.seed));
+
+    let mut terminal = ratatui::init();
+    let result = (|| -> io::Result<()> {
+        while !screen.game.is_finished() {
+            terminal.draw(|frame| draw(frame, &screen))?;
+
+            let key = match event::read()? {
+                event::Event::Key(key) if key.kind == KeyEventKind::Press => key,
+                _ => continue,
+            };
+            match key.code {
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    screen.message = match &session.save_path {
+                        Some(path) => {
+                            SavedGame::from_game(screen.game, session.seed, elapsed(started)).save(path)?;
+                            screen.reason = "saved";
+                            session.locale.text("saved").to_string()
+                        }
+                        None => session.locale.text("goodbye").to_string(),
+                    };
+                    screen.game.quit();
+                }
+                KeyCode::Esc => {
+                    screen.game.quit();
+                    screen.message = session.locale.text("goodbye").to_string();
+                }
+                KeyCode::Char(c) => screen.typed.push(c),
+                KeyCode::Backspace => {
+                    screen.typed.pop();
+                }
+                KeyCode::Enter => {
+                    let line = std::mem::take(&mut screen.typed);
+                    submit(&mut screen, &line, session, elapsed(started))?;
+                    session.update_checkpoint(screen.game, elapsed(started));
+                }
+                _ => {}
+            }
+        }
+
+        session.log_end(screen.game, screen.reason);
+
+        // leave the final board up until the player has seen it
+        if screen.game.status() == Status::Won || screen.game.status() == Status::Lost {
+            screen.done = true;
+            terminal.draw(|frame| draw(frame, &screen))?;
+            while !matches!(event::read()?, event::Event::Key(key) if key.kind == KeyEventKind::Press) {}
+        }
+        Ok(())
+    })();
+    ratatui::restore();
+    result?;
+
+    if let Some(checkpoint) = &session.checkpoint {
+        *checkpoint.lock().unwrap() = None;
+    }
+
+    // the alternate screen is gone now, so repeat the last reply where it stays visible
+    writeln!(io::stdout(), "{}", screen.message)?;
+
+    Ok(Summary {
+        status: screen.game.status(),
+        attempts: screen.game.attempts(),
+        secret: screen.game.secret(),
+        elapsed: elapsed(started),
+    })
+}
+
+// handles one line the way play_game does, but puts the reply in the message panel
+fn submit(screen: &mut Screen, line: &str, session: &Session, elapsed: Duration) -> io::Result<()> {
+    let game = &mut *screen.game;
+
+    if let Some(command) = command::parse_command(line) {
+        session.log(Event::command(line));
+        screen.message = match command {
+            Ok(Command::Save) => match &session.save_path {
+                Some(path) => {
+                    SavedGame::from_game(game, session.seed, elapsed).save(path)?;
+                    game.quit();
+                    screen.reason = "saved";
+                    session.locale.text("saved").to_string()
+                }
+                None => session.locale.text("save_unavailable").to_string(),
+            },
+            Ok(command) => {
+                let mut reply = Vec::new();
+                run_command(&mut reply, game, command, session.locale)?;
+                String::from_utf8_lossy(&reply).trim_end().to_string()
+            }
+            Err(unknown) => unknown.message(session.locale),
+        };
+        return Ok(());
+    }
+
+    let guess = match input::parse_guess(line, game.settings()) {
+        Ok(num) => num,
+        Err(invalid) => {
+            session.log(Event::invalid(line, invalid.to_string()));
+            screen.message = invalid.message(session.locale);
+            return Ok(());
+        }
+    };
+
+    session.log(Event::guess(guess));
+    let mut reply = match game.submit(guess) {
+        Outcome::Guess(ordering) => {
+            session.log(Event::result(ordering));
+            format!("{}: {}", guess, session.locale.describe(ordering))
+        }
+        Outcome::Finished => return Ok(()),
+    };
+    if game.settings().temperature {
+        if let Some(feedback) = temperature::feedback(game, session.locale) {
+            reply = format!("{}\n{}", reply, feedback);
+        }
+    }
+    if game.status() == Status::Lost {
+        let lost = session.locale.format("out_of_attempts", &[("secret", &game.secret())]);
+        reply = format!("{}\n{}", reply, lost);
+    }
+    screen.message = reply;
+    Ok(())
+}
+
+fn draw(frame: &mut Frame, screen: &Screen) {
+    let game = &screen.game;
+    let settings = game.settings();
+    let locale = screen.locale;
+    let message_height = screen.message.lines().count().max(1) as u16 + screen.done as u16 + 2;
+
+    let [title, bar, body, status, message, prompt] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(3),
+        Constraint::Min(6),
+        Constraint::Length(1),
+        Constraint::Length(message_height),
+        Constraint::Length(3),
+    ])
+    .areas(frame.area());
+    let [history, leaderboard] =
+        Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)]).areas(body);
+
+    frame.render_widget(
+        Paragraph::new(locale.format("tui_title", &[("low", &settings.low), ("high", &settings.high)]))
+        .style(Style::default().add_modifier(Modifier::BOLD)),
+        title,
+    );
+
+    let (low, high) = game.remaining();
+    let width = bar.width.saturating_sub(2);
+    frame.render_widget(
+        Paragraph::new(interval_bar(width, settings.low, settings.high, low, high))
+            .style(Style::default().fg(Color::Green))
+            .block(Block::bordered().title(locale.format("tui_still_possible", &[("low", &low), ("high", &high)]))),
+        bar,
+    );
+
+    // newest first, so the last guess is always on screen
+    let rows = game.history().iter().enumerate().rev().map(|(i, (guess, ordering))| {
+        let (arrow, color) = match ordering {
+            Ordering::Less => ("↑", Color::Yellow),
+            Ordering::Greater => ("↓", Color::Cyan),
+            Ordering::Equal => ("✓", Color::Green),
+        };
+        Row::new(vec![
+            (i + 1).to_string(),
+            guess.to_string(),
+            arrow.to_string(),
+            locale.describe(*ordering).to_string(),
+        ])
+        .style(Style::default().fg(color))
+    });
+    frame.render_widget(
+        Table::new(
+            rows,
+            [Constraint::Length(4), Constraint::Min(8), Constraint::Length(2), Constraint::Length(11)],
+        )
+        .header(
+            Row::new(vec!["#", locale.text("tui_guess_column"), "", ""])
+                .style(Style::default().add_modifier(Modifier::BOLD)),
+        )
+        .block(Block::bordered().title(locale.text("tui_guesses"))),
+        history,
+    );
+
+    let top = screen.top.iter().enumerate().map(|(rank, entry)| {
+        Row::new(vec![
+            format!("{}.", rank + 1),
+            entry.player.clone(),
+            entry.attempts.to_string(),
+            format!("{:.1}s", entry.duration().as_secs_f64()),
+        ])
+    });
+    let block = Block::bordered()
+        .title(locale.format("tui_top", &[("count", &TOP), ("difficulty", &settings.difficulty)]));
+    if screen.top.is_empty() {
+        frame.render_widget(Paragraph::new(locale.text("tui_no_wins")).block(block), leaderboard);
+    } else {
+        let widths = [Constraint::Length(4), Constraint::Min(8), Constraint::Length(4), Constraint::Length(8)];
+        frame.render_widget(Table::new(top, widths).block(block), leaderboard);
+    }
+
+    let attempts = match game.attempts_left() {
+        Some(left) => locale.format("tui_attempts_left", &[("attempts", &game.attempts()), ("left", &left)]),
+        None => locale.format("tui_attempts_no_limit", &[("attempts", &game.attempts())]),
+    };
+    frame.render_widget(Paragraph::new(attempts), status);
+
+    let mut lines: Vec<Line> = screen.message.lines().map(Line::from).collect();
+    if screen.done {
+        lines.push(Line::from(locale.text("tui_press_any_key")));
+    }
+    frame.render_widget(Paragraph::new(lines).block(Block::bordered()), message);
+
+    frame.render_widget(
+        Paragraph::new(format!("> {}", screen.typed)).block(Block::bordered().title(locale.text("tui_your_guess"))),
+        prompt,
+    );
+    frame.set_cursor_position((prompt.x + 3 + screen.typed.chars().count() as u16, prompt.y + 1));
+}
+
+// the whole range drawn `width` cells wide, with the part that can still hold the secret filled in
+pub fn interval_bar(width: u16, low: i128, high: i128, from: i128, to: i128) -> String {
+    if width == 0 {
+        return String::new();
+    }
+    // floats are plenty precise for picking cells, and can't overflow on huge ranges
+    let total = high as f64 - low as f64 + 1.0;
+    let cells = width as f64;
+    // rounding can push the last number of a huge range off the end, so keep it on the bar
+    let first = ((((from as f64 - low as f64) / total) * cells).floor() as u16).min(width - 1);
+    let last = ((((to as f64 - low as f64 + 1.0) / total) * cells).ceil() as u16).max(first + 1);
+
+    (0..width)
+        .map(|cell| if cell >= first && cell < last { '█' } else { '░' })
+        .collect()
+}
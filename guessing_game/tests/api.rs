@@ -0,0 +1,204 @@
+mod harness;
+
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::process::{Child, Stdio};
+use std::thread;
+use std::time::Duration;
+use serde_json::{json, Value};
+use guessing_game::api;
+use guessing_game::clock::ManualClock;
+use guessing_game::Settings;
+
+// kills the server even when an assertion fails part way through, and clears away its directory
+struct Server(Child, PathBuf);
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+        let _ = fs::remove_dir_all(&self.1);
+    }
+}
+
+// starts `guessing_game api` on a free port and returns it with its address
+fn start_server(ttl: u64) -> (Server, String) {
+    let dir = harness::temp_dir("api");
+    let mut child = harness::command(&dir, &["api", "--port", "0", "--seed", "7", "--ttl", &ttl.to_string()])
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to start the server");
+
+    let mut stdout = BufReader::new(child.stdout.take().unwrap());
+    let mut line = String::new();
+    let address = loop {
+        line.clear();
+        assert!(stdout.read_line(&mut line).unwrap() > 0, "server exited early");
+        if let Some(address) = line.trim().strip_prefix("Listening on http://") {
+            break address.to_string();
+        }
+    };
+
+    (Server(child, dir), address)
+}
+
+// sends one request and returns the status code with the JSON body
+fn request(address: &str, method: &str, path: &str, body: &str) -> (u16, Value) {
+    let mut stream = TcpStream::connect(address).unwrap();
+    write!(
+        stream,
+        "{} {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+        method,
+        path,
+        address,
+        body.len(),
+        body
+    )
+    .unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    let (head, body) = response.split_once("\r\n\r\n").expect("no end of headers");
+    let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+    (status, serde_json::from_str(body).unwrap())
+}
+
+fn create(address: &str, body: Value) -> String {
+    let (status, game) = request(address, "POST", "/games", &body.to_string());
+    assert_eq!(status, 201, "{}", game);
+    game["id"].as_str().unwrap().to_string()
+}
+
+#[test]
+fn a_game_is_played_to_the_end_over_http() {
+    let (_server, address) = start_server(60);
+    let id = create(&address, json!({ "low": 1, "high": 1000, "seed": 42, "max_attempts": 0 }));
+
+    let (status, game) = request(&address, "GET", &format!("/games/{}", id), "");
+    assert_eq!(status, 200);
+    assert_eq!(game["status"], "playing");
+    assert_eq!(game["max_attempts"], Value::Null);
+    assert!(game.get("secret").is_none(), "the secret leaked: {}", game);
+
+    let (mut low, mut high) = (1, 1000);
+    let mut guesses = 0;
+    let found = loop {
+        let guess = (low + high) / 2;
+        let (status, reply) = request(
+            &address,
+            "POST",
+            &format!("/games/{}/guesses", id),
+            &json!({ "guess": guess }).to_string(),
+        );
+        assert_eq!(status, 200, "{}", reply);
+        guesses += 1;
+        match reply["ordering"].as_str().unwrap() {
+            "Less" => low = guess + 1,
+            "Greater" => high = guess - 1,
+            "Equal" => break guess,
+            other => panic!("unexpected ordering {}", other),
+        }
+    };
+
+    let (_, game) = request(&address, "GET", &format!("/games/{}", id), "");
+    assert_eq!(game["status"], "won");
+    assert_eq!(game["secret"], found);
+    assert_eq!(game["attempts"], guesses);
+    assert_eq!(game["history"].as_array().unwrap().len(), guesses);
+
+    let (status, _) = request(&address, "POST", &format!("/games/{}/guesses", id), r#"{"guess": 1}"#);
+    assert_eq!(status, 409);
+}
+
+#[test]
+fn running_out_of_attempts_reveals_the_secret() {
+    let (_server, address) = start_server(60);
+    // seed 42 puts the secret above 50, so guessing 1 always misses
+    let id = create(&address, json!({ "low": 1, "high": 100, "max_attempts": 1, "seed": 42 }));
+
+    let (_, reply) = request(&address, "POST", &format!("/games/{}/guesses", id), r#"{"guess": 1}"#);
+    assert_eq!(reply["ordering"], "Less");
+    assert_eq!(reply["status"], "lost");
+    assert_eq!(reply["attempts_left"], 0);
+
+    let (_, game) = request(&address, "GET", &format!("/games/{}", id), "");
+    assert!(game["secret"].as_i64().unwrap() > 1);
+}
+
+#[test]
+fn bad_requests_get_json_errors() {
+    let (_server, address) = start_server(60);
+    let id = create(&address, json!({}));
+    let guesses = format!("/games/{}/guesses", id);
+
+    let cases = [
+        ("GET", String::from("/games/nope"), "", 404),
+        ("GET", String::from("/elsewhere"), "", 404),
+        ("DELETE", format!("/games/{}", id), "", 405),
+        ("POST", String::from("/games"), "{not json", 400),
+        ("POST", String::from("/games"), r#"{"colour": "blue"}"#, 400),
+        ("POST", String::from("/games"), r#"{"low": 10, "high": 1}"#, 422),
+        ("POST", guesses.clone(), "", 400),
+        ("POST", guesses, r#"{"guess": 1000}"#, 422),
+    ];
+    for (method, path, body, expected) in cases {
+        let (status, reply) = request(&address, method, &path, body);
+        assert_eq!(status, expected, "{} {} {}", method, path, body);
+        assert!(reply["error"].is_string(), "{}", reply);
+    }
+
+    // none of that used up an attempt
+    let (_, game) = request(&address, "GET", &format!("/games/{}", id), "");
+    assert_eq!(game["attempts"], 0);
+}
+
+#[test]
+fn oversized_headers_are_refused() {
+    let (_server, address) = start_server(60);
+
+    // a long header under the cap is fine
+    let mut stream = TcpStream::connect(&address).unwrap();
+    write!(stream, "GET /games/nope HTTP/1.1\r\nCookie: {}\r\n\r\n", "a".repeat(4096)).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"), "{}", response);
+
+    // exactly 8 KiB with no end of headers in sight
+    let start = "GET / HTTP/1.1\r\nX-Filler: ";
+    let mut stream = TcpStream::connect(&address).unwrap();
+    write!(stream, "{}{}", start, "a".repeat(8 * 1024 - start.len())).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 431 Request Header Fields Too Large\r\n"), "{}", response);
+    let body: Value = serde_json::from_str(response.split_once("\r\n\r\n").unwrap().1).unwrap();
+    assert_eq!(body["error"], "the request headers are too large");
+}
+
+#[test]
+fn idle_games_expire() {
+    // the server runs in this process on a clock the test moves, rather than waiting out the ttl
+    let clock: &'static ManualClock = Box::leak(Box::new(ManualClock::new()));
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    let settings = Settings::default();
+    thread::spawn(move || api::serve(listener, settings, 7, Duration::from_secs(60), clock));
+
+    let id = create(&address, json!({}));
+    let path = format!("/games/{}", id);
+
+    clock.advance(Duration::from_secs(59));
+    let (status, _) = request(&address, "GET", &path, "");
+    assert_eq!(status, 200);
+
+    // looking at a game counts as using it, so it has another full minute from here
+    clock.advance(Duration::from_secs(59));
+    let (status, _) = request(&address, "GET", &path, "");
+    assert_eq!(status, 200);
+
+    clock.advance(Duration::from_secs(61));
+    let (status, reply) = request(&address, "GET", &path, "");
+    assert_eq!(status, 404);
+    assert!(reply["error"].as_str().unwrap().contains("expired"));
+}
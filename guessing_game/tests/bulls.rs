@@ -0,0 +1,142 @@
+mod harness;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use guessing_game::bulls::{bulls_and_cows, solve_game, validate_solver, BullsSettings, Code, InvalidCode, Score, Solver};
+use guessing_game::locale::Locale;
+use guessing_game::{Difficulty, Status};
+
+fn code(text: &str) -> Code {
+    Code::parse(text, text.trim().len()).unwrap()
+}
+
+fn score(bulls: usize, cows: usize) -> Score {
+    Score { bulls, cows }
+}
+
+#[test]
+fn bulls_are_in_place_and_cows_are_elsewhere() {
+    assert_eq!(code("1234").score(&code("1234")), score(4, 0));
+    assert_eq!(code("1234").score(&code("4321")), score(0, 4));
+    assert_eq!(code("1234").score(&code("1243")), score(2, 2));
+    assert_eq!(code("1234").score(&code("5678")), score(0, 0));
+    assert_eq!(code("0123").score(&code("9310")), score(0, 3));
+    assert_eq!(code("0123").score(&code("0987")), score(1, 0));
+
+    // scoring is symmetric
+    for (a, b) in [("1234", "2419"), ("9876", "6789"), ("0159", "9510")] {
+        assert_eq!(code(a).score(&code(b)), code(b).score(&code(a)));
+    }
+
+    assert_eq!(score(1, 0).to_string(), "1 bull, 0 cows");
+    assert_eq!(score(2, 1).to_string(), "2 bulls, 1 cow");
+}
+
+#[test]
+fn only_codes_of_distinct_digits_are_accepted() {
+    assert_eq!(Code::parse(" 0123\n", 4).map(|code| code.to_string()), Ok(String::from("0123")));
+    assert_eq!(Code::parse("123", 4), Err(InvalidCode::WrongLength { expected: 4, got: 3 }));
+    assert_eq!(Code::parse("12a4", 4), Err(InvalidCode::NotADigit('a')));
+    assert_eq!(Code::parse("1231", 4), Err(InvalidCode::Repeated(1)));
+    assert_eq!(Code::parse("-123", 4), Err(InvalidCode::NotADigit('-')));
+    assert_eq!(InvalidCode::Repeated(1).to_string(), "1 appears more than once; every digit is different.");
+
+    assert_eq!([1, 2, 3, 4].map(|digits| Code::all(digits).len()), [10, 90, 720, 5040]);
+
+    let settings = |digits, max_attempts| BullsSettings {
+        difficulty: Difficulty::Custom,
+        digits,
+        max_attempts,
+    };
+    assert!(settings(10, Some(1)).validate().is_ok());
+    assert!(settings(0, None).validate().is_err());
+    assert!(settings(11, None).validate().is_err());
+    assert!(settings(4, Some(0)).validate().is_err());
+    assert_eq!(BullsSettings::preset(Difficulty::Hard).digits, 5);
+
+    assert!(validate_solver(5).is_ok());
+    assert_eq!(validate_solver(6), Err(String::from("the solver handles codes of 1 to 5 digits")));
+    assert!(validate_solver(0).is_err());
+}
+
+// plays the solver against one secret and returns how many guesses it took
+fn crack(secret: &Code, digits: usize) -> u32 {
+    let mut solver = Solver::new(digits);
+    for guesses in 1.. {
+        let guess = solver.next_guess();
+        let score = secret.score(&guess);
+        if score.bulls == digits {
+            return guesses;
+        }
+        solver.record(&guess, score);
+        assert!(solver.candidates().contains(secret), "{} was ruled out", secret);
+    }
+    unreachable!()
+}
+
+#[test]
+fn the_solver_cracks_short_codes_in_six_guesses() {
+    // every two digit code, and a spread of three digit ones
+    let secrets = Code::all(2).into_iter().chain(Code::all(3).into_iter().step_by(24));
+    for secret in secrets {
+        let digits = secret.to_string().len();
+        let guesses = crack(&secret, digits);
+        assert!(guesses <= 6, "{} took {} guesses", secret, guesses);
+    }
+}
+
+#[test]
+fn the_solver_cracks_longer_codes_in_a_few_guesses() {
+    for (digits, seeds, most) in [(4, 0..4, 7), (5, 0..1, 8)] {
+        for seed in seeds {
+            let mut output = Vec::new();
+            let guesses = solve_game(&mut output, digits, &mut StdRng::seed_from_u64(seed), Locale::En).unwrap();
+            let output = String::from_utf8(output).unwrap();
+            assert!(guesses <= most, "{} digits, seed {}:\n{}", digits, seed, output);
+            assert!(output.ends_with(&format!("found in {} guesses.\n", guesses)), "{}", output);
+        }
+    }
+}
+
+#[test]
+fn hints_follow_the_solver_and_are_refused_for_long_codes() {
+    let play = |digits: usize, input: &str| {
+        let settings = BullsSettings {
+            difficulty: Difficulty::Custom,
+            digits,
+            max_attempts: None,
+        };
+        let mut output = Vec::new();
+        let mut rng = StdRng::seed_from_u64(4);
+        let summary = bulls_and_cows(input.as_bytes(), &mut output, settings, &mut rng, Locale::En).unwrap();
+        (summary, String::from_utf8(output).unwrap())
+    };
+
+    let (summary, output) = play(3, ":hint\n012\n:hint\n:giveup\n");
+    assert_eq!(summary.status, Status::GaveUp);
+    assert!(output.contains("Hint: 720 codes are still possible. Try 012."), "{}", output);
+    assert_eq!(output.matches("Hint: ").count(), 2, "{}", output);
+
+    let (_, output) = play(6, ":hint\n:quit\n");
+    assert!(output.contains("No hints for codes of more than 5 digits."), "{}", output);
+
+    let dir = harness::temp_dir("bulls");
+    let solver = harness::run(&dir, &["bulls", "--solver", "--digits", "6", "--seed", "1"], "");
+    assert_eq!(solver.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&solver.stderr).contains("the solver handles codes of 1 to 5 digits"));
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn the_game_speaks_the_players_language() {
+    let settings = BullsSettings::preset(Difficulty::Easy);
+    let mut output = Vec::new();
+    let mut rng = StdRng::seed_from_u64(4);
+    bulls_and_cows("12\n:giveup\n".as_bytes(), &mut output, settings, &mut rng, Locale::De).unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(output.starts_with("Bullen und Kühe!\n"), "{}", output);
+    assert!(output.contains(&format!("Der Code hat {} Ziffern, nicht 2.", settings.digits)), "{}", output);
+    assert!(output.contains("Du hast nach 0 Versuchen aufgegeben."), "{}", output);
+    assert_eq!(score(1, 2).message(Locale::De), "1 Bulle, 2 Kühe");
+}
@@ -0,0 +1,64 @@
+use std::io::Cursor;
+use guessing_game::command::{parse_command, Command, UnknownCommand};
+use guessing_game::{play_game, Game, Session, Settings, Status, Summary};
+
+#[test]
+fn lines_starting_with_a_colon_are_commands() {
+    assert_eq!(parse_command("42"), None);
+    assert_eq!(parse_command(""), None);
+    assert_eq!(parse_command("  :QUIT \n"), Some(Ok(Command::Quit)));
+    assert_eq!(parse_command(":q"), Some(Ok(Command::Quit)));
+    assert_eq!(parse_command(":?"), Some(Ok(Command::Help)));
+    assert_eq!(parse_command(":dance"), Some(Err(UnknownCommand(String::from(":dance")))));
+
+    // every command can be typed back in under the name :help shows for it
+    for command in Command::ALL {
+        assert_eq!(parse_command(command.name()), Some(Ok(command)));
+    }
+}
+
+fn play(input: &str) -> (Game, Summary, String) {
+    let mut game = Game::with_secret(Settings::default(), 42);
+    let mut output = Vec::new();
+    let summary = play_game(Cursor::new(input), &mut output, &mut game, &Session::default()).unwrap();
+    (game, summary, String::from_utf8(output).unwrap())
+}
+
+#[test]
+fn hint_and_history_follow_the_guesses() {
+    let (game, summary, output) = play(":history\n:hint\n20\n60\n:hint\n:history\n:dance\n42\n");
+
+    assert_eq!(summary.status, Status::Won);
+    // commands are never counted as attempts
+    assert_eq!(game.attempts(), 3);
+    assert!(output.contains("No guesses yet."), "{}", output);
+    assert!(output.contains("Hint: the secret number is between 1 and 100."), "{}", output);
+    assert!(output.contains("Hint: the secret number is between 21 and 59."), "{}", output);
+    assert!(output.contains("  1. 20         Less (Too small!)\n  2. 60         Greater (Too big!)\n"), "{}", output);
+    assert!(output.contains("Unknown command ':dance'."), "{}", output);
+}
+
+#[test]
+fn quit_and_giveup_end_the_game_differently() {
+    let (_, summary, output) = play("50\n:giveup\n");
+    assert_eq!(summary.status, Status::GaveUp);
+    assert_eq!(summary.status.exit_code(), 1);
+    assert!(output.contains("42"), "{}", output);
+
+    let (_, summary, _) = play(":quit\n50\n");
+    assert_eq!(summary.status, Status::Quit);
+    assert_eq!(summary.attempts, 0);
+
+    // without a save path :save can't do anything, so the game goes on
+    let (_, summary, output) = play(":save\n42\n");
+    assert_eq!(summary.status, Status::Won);
+    assert!(output.contains("Saving is not available"), "{}", output);
+}
+
+#[test]
+fn help_lists_every_command() {
+    let (_, _, output) = play(":help\n:quit\n");
+    for command in Command::ALL {
+        assert!(output.contains(command.name()), "no {} in:\n{}", command, output);
+    }
+}
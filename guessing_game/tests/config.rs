@@ -0,0 +1,112 @@
+mod harness;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Output;
+
+// a directory of its own for each test, holding `contents` as the default config.toml
+fn config_home(test: &str, contents: &str) -> PathBuf {
+    let home = harness::temp_dir(&format!("config-{}", test));
+    let dir = home.join("config").join("guessing_game");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("config.toml"), contents).unwrap();
+    home
+}
+
+fn run(home: &Path, env: &[(&str, &str)], args: &[&str], input: &str) -> Output {
+    let mut command = harness::command(home, args);
+    command.envs(env.iter().copied());
+    harness::run_with(command, input)
+}
+
+// the value and source `config show` printed for a key
+fn shown(output: &Output, key: &str) -> (String, String) {
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let line = stdout
+        .lines()
+        .find(|line| line.split_whitespace().next() == Some(key))
+        .unwrap_or_else(|| panic!("no {} in:\n{}", key, stdout));
+    let mut words = line.split_whitespace().skip(1);
+    let value = words.next().unwrap().to_string();
+    (value, words.collect::<Vec<_>>().join(" "))
+}
+
+#[test]
+fn flags_beat_the_config_file_which_beats_the_environment() {
+    let home = config_home("precedence", "difficulty = \"hard\"\nlang = \"de\"\nname = \"ana\"\n");
+    let env = [("LANG", "es_ES.UTF-8"), ("NO_COLOR", "1")];
+
+    let output = run(&home, &env, &["config", "show"], "");
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(shown(&output, "difficulty"), (String::from("hard"), String::from("config file")));
+    assert_eq!(shown(&output, "max"), (String::from("1000"), String::from("default")));
+    assert_eq!(shown(&output, "lang"), (String::from("de"), String::from("config file")));
+    assert_eq!(shown(&output, "name"), (String::from("ana"), String::from("config file")));
+    assert_eq!(shown(&output, "color"), (String::from("never"), String::from("$NO_COLOR")));
+
+    // a range flag replaces the config's range as a whole rather than mixing with it
+    let output = run(&home, &env, &["config", "show", "--max", "50", "--name", "bo", "--color", "always"], "");
+    assert_eq!(shown(&output, "difficulty"), (String::from("custom"), String::from("default")));
+    assert_eq!(shown(&output, "max"), (String::from("50"), String::from("--max")));
+    assert_eq!(shown(&output, "name"), (String::from("bo"), String::from("--name")));
+    assert_eq!(shown(&output, "color"), (String::from("always"), String::from("--color")));
+
+    // and the game itself plays by the same rules
+    let output = run(&home, &env, &["--seed", "1"], "");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Ich denke an eine Zahl zwischen 1 und 1000."), "{}", stdout);
+    let _ = fs::remove_dir_all(&home);
+}
+
+#[test]
+fn config_given_with_a_flag_replaces_the_default_one() {
+    let home = config_home("flag", "lang = \"de\"\n");
+    let other = home.join("other.toml");
+    fs::write(&other, "min = 10\nmax = 20\nattempts = 0\ncolor = \"always\"\n").unwrap();
+
+    let output = run(&home, &[], &["--config", other.to_str().unwrap(), "--seed", "1"], "15\n");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("I'm thinking of a number between 10 and 20.\n"), "{}", stdout);
+    assert!(stdout.contains("\x1b["), "no colour in:\n{}", stdout);
+    let _ = fs::remove_dir_all(&home);
+}
+
+#[test]
+fn a_broken_config_is_an_error() {
+    let home = config_home("broken", "");
+    let cases = [
+        ("colour = \"always\"\n", "unknown field `colour`"),
+        ("difficulty = \"easy\"\nmax = 5\n", "cannot be combined"),
+        ("lang = \"fr\"\n", "unknown language"),
+        ("min = 10\nmax = 1\n", "invalid range"),
+        ("attempts = -1\n", "invalid value"),
+    ];
+    for (contents, expected) in cases {
+        let path = home.join("broken.toml");
+        fs::write(&path, contents).unwrap();
+        let output = run(&home, &[], &["config", "show", "--config", path.to_str().unwrap()], "");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert_eq!(output.status.code(), Some(2), "{}", contents);
+        assert!(stderr.contains(expected), "{:?} gave {}", contents, stderr);
+    }
+
+    let missing = home.join("missing.toml");
+    let output = run(&home, &[], &["--config", missing.to_str().unwrap()], "");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("no config file"));
+    let _ = fs::remove_dir_all(&home);
+}
+
+#[test]
+fn commands_that_ignore_the_config_run_despite_a_broken_one() {
+    let home = config_home("ignored", "difficulty = \"bogus\"\n");
+    for args in [&["leaderboard"][..], &["stats"][..]] {
+        let output = run(&home, &[], args, "");
+        assert!(output.status.success(), "{:?}: {}", args, String::from_utf8_lossy(&output.stderr));
+    }
+
+    let output = run(&home, &[], &["--seed", "1"], "");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unknown variant `bogus`"));
+    let _ = fs::remove_dir_all(&home);
+}
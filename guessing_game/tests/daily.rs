@@ -0,0 +1,92 @@
+mod harness;
+
+use std::fs;
+use guessing_game::cli::parse_args;
+use guessing_game::daily::{self, DailyLog};
+use guessing_game::Game;
+
+#[test]
+fn every_build_picks_the_same_secret_for_a_day() {
+    // pinned: changing either breaks every shared result for that day
+    assert_eq!(daily::seed("2024-01-01"), 4799318448991248404);
+    assert_eq!(daily::secret("2024-01-01", &daily::settings()), 5);
+    assert_eq!(daily::secret("2024-01-02", &daily::settings()), 38);
+
+    let settings = daily::settings();
+    let secrets: Vec<i128> = (1..=28).map(|day| daily::secret(&format!("2024-02-{:02}", day), &settings)).collect();
+    assert!(secrets.iter().all(|&secret| settings.contains(secret)));
+    assert!(secrets.iter().any(|&secret| secret != secrets[0]));
+}
+
+#[test]
+fn the_share_gives_away_the_arrows_but_not_the_numbers() {
+    let mut game = Game::with_secret(daily::settings(), 5);
+    for guess in [50, 3, 5] {
+        game.submit(guess);
+    }
+    let share = daily::share("2024-01-01", &game);
+    assert_eq!(share, "Guess the number daily 2024-01-01: 3/10\n⬇️⬆️🎯");
+    assert!(!share.contains("50"));
+
+    let mut game = Game::with_secret(daily::settings(), 5);
+    game.submit(50);
+    game.quit();
+    assert_eq!(daily::share("2024-01-01", &game), "Guess the number daily 2024-01-01: -/10\n⬇️");
+}
+
+#[test]
+fn each_player_gets_one_go_a_day() {
+    let dir = std::env::temp_dir().join(format!("guessing_game-daily-log-{}", std::process::id()));
+    let path = dir.join("daily.json");
+
+    let mut log = DailyLog::load(&path).unwrap();
+    assert!(log.find("ana", "2024-01-01").is_none());
+    log.record("ana", "2024-01-01", None);
+    log.record("ana", "2024-01-01", Some(String::from("3/10")));
+    log.record("bo", "2024-01-01", None);
+    log.save(&path).unwrap();
+
+    let log = DailyLog::load(&path).unwrap();
+    assert_eq!(log.find("ana", "2024-01-01").unwrap().share.as_deref(), Some("3/10"));
+    assert_eq!(log.find("bo", "2024-01-01").unwrap().share, None);
+    assert!(log.find("ana", "2024-01-02").is_none());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn the_daily_puzzle_picks_its_own_settings() {
+    let args = |line: &str| line.split_whitespace().map(String::from).collect::<Vec<_>>();
+    assert_eq!(
+        parse_args(args("--daily --adaptive")).unwrap_err(),
+        "--adaptive can't be combined with --daily"
+    );
+    assert_eq!(
+        parse_args(args("--adaptive --daily")).unwrap_err(),
+        "--adaptive can't be combined with --daily"
+    );
+    assert!(parse_args(args("--daily")).is_ok());
+}
+
+#[test]
+fn todays_secret_is_found_once_and_the_result_kept() {
+    let dir = harness::temp_dir("daily");
+    let date = daily::today();
+    let secret = daily::secret(&date, &daily::settings());
+
+    let first = harness::run(&dir, &["--daily"], &format!("{}\n", secret));
+    let stdout = String::from_utf8_lossy(&first.stdout);
+    assert_eq!(first.status.code(), Some(0), "{}", stdout);
+    assert!(stdout.contains(&format!("Guess the number daily {}: 1/10\n🎯", date)), "{}", stdout);
+
+    let again = harness::run(&dir, &["--daily"], "");
+    let stdout = String::from_utf8_lossy(&again.stdout);
+    assert_eq!(again.status.code(), Some(3));
+    assert!(stdout.contains("tester has already played the daily puzzle"), "{}", stdout);
+    assert!(stdout.contains("1/10\n🎯"), "{}", stdout);
+
+    let adaptive = harness::run(&dir, &["--daily", "--adaptive"], "");
+    assert_eq!(adaptive.status.code(), Some(2));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
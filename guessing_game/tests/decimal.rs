@@ -0,0 +1,117 @@
+mod harness;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use guessing_game::decimal::{decimal_game, format_decimal, parse_guess, DecimalSettings, InvalidDecimal};
+use guessing_game::locale::Locale;
+use guessing_game::Status;
+
+#[test]
+fn guesses_are_read_to_the_precision() {
+    let settings = DecimalSettings::default();
+
+    assert_eq!(parse_guess("3.14", &settings), Ok((314, false)));
+    assert_eq!(parse_guess(" 7 ", &settings), Ok((700, false)));
+    assert_eq!(parse_guess(".5", &settings), Ok((50, false)));
+    assert_eq!(parse_guess("2.50000", &settings), Ok((250, false)));
+    assert_eq!(parse_guess("2.5e0", &settings), Ok((250, false)));
+    // anything within half a step lands on the same value
+    assert_eq!(parse_guess("3.14159", &settings), Ok((314, true)));
+    assert_eq!(parse_guess("3.145", &settings), Ok((315, true)));
+    assert_eq!(parse_guess("9.999", &settings), Ok((1000, true)));
+}
+
+#[test]
+fn nan_infinity_and_junk_are_refused() {
+    let settings = DecimalSettings::default();
+
+    for nan in ["NaN", "nan", "-NaN"] {
+        assert_eq!(parse_guess(nan, &settings), Err(InvalidDecimal::NaN), "{}", nan);
+    }
+    for infinity in ["inf", "-inf", "Infinity", "+infinity"] {
+        assert_eq!(parse_guess(infinity, &settings), Err(InvalidDecimal::Infinite), "{}", infinity);
+    }
+    assert_eq!(parse_guess("1e999", &settings), Err(InvalidDecimal::Overflow));
+    assert_eq!(parse_guess("99999999999999999999999999999999999999999", &settings), Err(InvalidDecimal::Overflow));
+    assert_eq!(parse_guess("", &settings), Err(InvalidDecimal::Empty));
+    for junk in ["abc", "1.2.3", ".", "-", "1,5"] {
+        assert_eq!(parse_guess(junk, &settings), Err(InvalidDecimal::NotANumber(junk.to_string())), "{}", junk);
+    }
+
+    // 10.004 is within half a step of 10.00, but 10.005 is past it
+    assert_eq!(parse_guess("10.004", &settings), Ok((1000, true)));
+    let out_of_range = parse_guess("10.005", &settings).unwrap_err();
+    assert_eq!(out_of_range.to_string(), "10.01 is out of range; guess between 0.00 and 10.00.");
+}
+
+#[test]
+fn numbers_are_shown_with_every_place() {
+    assert_eq!(format_decimal(314, 2), "3.14");
+    assert_eq!(format_decimal(5, 3), "0.005");
+    assert_eq!(format_decimal(-150, 2), "-1.50");
+    assert_eq!(format_decimal(-5, 1), "-0.5");
+    assert_eq!(format_decimal(42, 0), "42");
+}
+
+fn play(settings: DecimalSettings, lines: &[String]) -> (guessing_game::Summary, String) {
+    let mut output = Vec::new();
+    let mut rng = StdRng::seed_from_u64(9);
+    let summary = decimal_game(lines.join("\n").as_bytes(), &mut output, settings, &mut rng, Locale::En).unwrap();
+    (summary, String::from_utf8(output).unwrap())
+}
+
+#[test]
+fn binary_search_finds_a_decimal_secret() {
+    let settings = DecimalSettings {
+        low: -1,
+        high: 1,
+        places: 3,
+        max_attempts: None,
+    };
+
+    // the seed fixes the secret, so giving up once shows what the bisection below will look for
+    let (given_up, output) = play(settings, &[String::from(":giveup")]);
+    assert_eq!(given_up.status, Status::GaveUp);
+    assert!(output.contains("between -1.000 and 1.000, to 3 decimal places"), "{}", output);
+    let secret = given_up.secret;
+
+    let (mut low, mut high) = (-1000i128, 1000i128);
+    let mut lines = Vec::new();
+    loop {
+        let guess = (low + high).div_euclid(2);
+        lines.push(format_decimal(guess, 3));
+        match guess.cmp(&secret) {
+            std::cmp::Ordering::Less => low = guess + 1,
+            std::cmp::Ordering::Greater => high = guess - 1,
+            std::cmp::Ordering::Equal => break,
+        }
+    }
+
+    let (summary, output) = play(settings, &lines);
+    assert_eq!(summary.status, Status::Won);
+    assert!(summary.attempts <= 11, "took {} guesses", summary.attempts);
+    let winning = format!("You guessed: {}\nYou win!", format_decimal(secret, 3));
+    assert!(output.contains(&winning), "{}", output);
+}
+
+#[test]
+fn the_widest_ranges_get_a_default_limit_without_overflowing() {
+    let wide = |low, high, places| DecimalSettings {
+        low,
+        high,
+        places,
+        max_attempts: None,
+    };
+    // 2 * 10^38 steps of 10^-18 need 128 guesses
+    let limit = wide(-100_000_000_000_000_000_000, 100_000_000_000_000_000_000, 18).default_attempts();
+    assert_eq!(limit, 131);
+    assert_eq!(wide(i128::MIN, i128::MAX, 0).default_attempts(), 131);
+
+    let dir = harness::temp_dir("decimal");
+    let args = ["decimal", "--min=-100000000000000000000", "--max=100000000000000000000", "--places=18", "--seed", "1"];
+    let run = harness::run(&dir, &args, "");
+    let stdout = String::from_utf8_lossy(&run.stdout);
+    assert_eq!(run.status.code(), Some(3), "{}\n{}", stdout, String::from_utf8_lossy(&run.stderr));
+    assert!(stdout.contains("You have 131 attempts."), "{}", stdout);
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,70 @@
+use std::cmp::Ordering;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use guessing_game::cli::parse_args;
+use guessing_game::reverse::midpoint;
+use guessing_game::{Difficulty, Game, Outcome, Settings, Status};
+
+fn settings(line: &str) -> Result<Settings, String> {
+    parse_args(line.split_whitespace().map(String::from))?.settings()
+}
+
+#[test]
+fn presets_set_the_range_and_the_limit() {
+    assert_eq!(Difficulty::Easy.settings(), Settings::new(Difficulty::Easy, 1, 50, Some(10)));
+    assert_eq!(Difficulty::Normal.settings(), Settings::new(Difficulty::Normal, 1, 100, Some(10)));
+    assert_eq!(Difficulty::Hard.settings(), Settings::new(Difficulty::Hard, 1, 1000, Some(10)));
+    assert_eq!(Difficulty::Normal.settings().size(), 100);
+    // the whole i128 range is one too many to count
+    assert_eq!(Settings::new(Difficulty::Custom, i128::MIN, i128::MAX, None).size(), u128::MAX);
+
+    for difficulty in Difficulty::ALL {
+        assert_eq!(difficulty.name().parse(), Ok(difficulty));
+    }
+    assert_eq!(" HARD ".parse(), Ok(Difficulty::Hard));
+    assert_eq!("brutal".parse::<Difficulty>(), Err(String::from("unknown difficulty: brutal")));
+}
+
+#[test]
+fn flags_pick_a_preset_or_a_custom_range() {
+    assert_eq!(settings(""), Ok(Settings::default()));
+    assert_eq!(settings("--difficulty easy"), Ok(Difficulty::Easy.settings()));
+    assert_eq!(settings("--min -5 --max 5"), Ok(Settings::new(Difficulty::Custom, -5, 5, Some(10))));
+    assert_eq!(settings("--attempts 0"), Ok(Settings::new(Difficulty::Custom, 1, 100, None)));
+    assert_eq!(
+        settings("--difficulty custom --max 7 --attempts 3"),
+        Ok(Settings::new(Difficulty::Custom, 1, 7, Some(3)))
+    );
+
+    assert!(settings("--difficulty hard --max 7").unwrap_err().contains("cannot be combined"));
+    assert_eq!(settings("--min 10 --max 1").unwrap_err(), "invalid range: 10 is greater than 1");
+    assert_eq!(settings("--max ten").unwrap_err(), "invalid value for --max: ten");
+    assert_eq!(settings("--attempts -1").unwrap_err(), "invalid value for --attempts: -1");
+}
+
+#[test]
+fn the_widest_ranges_can_be_played() {
+    let ranges = [
+        (i64::MIN as i128, i64::MAX as i128),
+        (0, u64::MAX as i128),
+        (i128::MIN, i128::MAX),
+        (i128::MAX, i128::MAX),
+    ];
+    for (low, high) in ranges {
+        let settings = Settings::new(Difficulty::Custom, low, high, None);
+        assert_eq!(settings.validate(), Ok(()));
+
+        let mut game = Game::new(settings, &mut StdRng::seed_from_u64(1));
+        assert!(settings.contains(game.secret()));
+
+        // bisecting without overflow finds the secret in at most 129 guesses
+        while !game.is_finished() {
+            let (low, high) = game.remaining();
+            let guess = midpoint(low, high);
+            assert_ne!(game.submit(guess), Outcome::Finished);
+        }
+        assert_eq!(game.status(), Status::Won);
+        assert!(game.attempts() <= 129, "{} guesses for {}..={}", game.attempts(), low, high);
+        assert_eq!(game.history().last().map(|(_, ordering)| *ordering), Some(Ordering::Equal));
+    }
+}
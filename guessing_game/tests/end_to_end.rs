@@ -0,0 +1,93 @@
+mod harness;
+
+use std::fs;
+use std::path::Path;
+use harness::{matches, replay, Session};
+
+// every golden transcript under tests/transcripts, each played by a test below
+const TRANSCRIPTS: [&str; 6] = ["win", "invalid_input", "eof", "give_up", "out_of_attempts", "commands"];
+
+#[test]
+fn win() {
+    replay("win");
+}
+
+#[test]
+fn invalid_input() {
+    replay("invalid_input");
+}
+
+#[test]
+fn eof() {
+    replay("eof");
+}
+
+#[test]
+fn give_up() {
+    replay("give_up");
+}
+
+#[test]
+fn out_of_attempts() {
+    replay("out_of_attempts");
+}
+
+#[test]
+fn commands() {
+    replay("commands");
+}
+
+#[test]
+fn no_transcript_is_left_unplayed() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/transcripts");
+    let mut found: Vec<String> = fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .map(|path| path.file_stem().unwrap().to_string_lossy().into_owned())
+        .collect();
+    found.sort();
+    let mut listed = TRANSCRIPTS.map(String::from).to_vec();
+    listed.sort();
+    assert_eq!(found, listed);
+}
+
+#[test]
+fn wildcards_match_any_text() {
+    assert!(matches("You win!", "You win!"));
+    assert!(!matches("You win!", "You win!!"));
+    assert!(matches("took {..}s", "took 0.0s"));
+    assert!(matches("{..} attempts {..}", "  1. tester  6 attempts 0.1s"));
+    assert!(matches("a{..}b{..}c", "abc"));
+    assert!(!matches("a{..}b{..}c", "acb"));
+}
+
+// not every check needs a golden file: this player reads each reply and bisects, whatever the seed
+#[test]
+fn a_bisecting_player_always_wins_in_time() {
+    for seed in ["1", "2", "3", "1000", "123456789"] {
+        let mut session = Session::spawn(&["--seed", seed]);
+        session.expect(&format!("Seed: {}", seed));
+        session.expect_eventually("I'm thinking of a number between 1 and 100. You have 10 attempts.");
+
+        let (mut low, mut high) = (1, 100);
+        loop {
+            session.expect_eventually("Please input your guess.");
+            let guess = (low + high) / 2;
+            session.send(&guess.to_string());
+            session.expect(&format!("You guessed: {}", guess));
+            match session.next_line().as_deref() {
+                Some("Too small!") => low = guess + 1,
+                Some("Too big!") => high = guess - 1,
+                Some("You win!") => break,
+                other => panic!("seed {}: unexpected reply {:?}", seed, other),
+            }
+        }
+
+        session.expect("Rating: 1200 -> {..}");
+        session.expect("");
+        session.expect("Top 10 (normal):");
+        session.expect("  1. tester {..}");
+        session.expect_exit(0);
+    }
+}
@@ -0,0 +1,132 @@
+mod harness;
+
+use std::fs;
+use std::io::{Cursor, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use guessing_game::events::{self, Event, EventLog, Kind, KEEP};
+use guessing_game::{play_game, Difficulty, Game, Session, Settings, Status};
+
+fn temp_dir(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("guessing_game-events-{}-{}", name, std::process::id()))
+}
+
+fn logged_session(path: &Path) -> Session {
+    Session {
+        seed: 9,
+        log: Some(Arc::new(Mutex::new(EventLog::create(path).unwrap()))),
+        ..Session::default()
+    }
+}
+
+#[test]
+fn a_game_is_logged_as_it_is_played_and_replays_the_same() {
+    let dir = temp_dir("replay");
+    let path = dir.join("game.jsonl");
+
+    let mut game = Game::with_secret(Settings::default(), 42);
+    let input = Cursor::new("abc\n:history\n50\n42\n");
+    let summary = play_game(input, &mut Vec::new(), &mut game, &logged_session(&path)).unwrap();
+    assert_eq!(summary.status, Status::Won);
+
+    let events = events::load(&path).unwrap();
+    let kinds: Vec<Kind> = events.iter().map(|event| event.event).collect();
+    assert_eq!(
+        kinds,
+        [Kind::Start, Kind::Invalid, Kind::Command, Kind::Guess, Kind::Result, Kind::Guess, Kind::Result, Kind::Win]
+    );
+    assert_eq!(events[0].seed, Some(9));
+    assert_eq!(events[0].secret, Some(42));
+    assert_eq!(events[2].input.as_deref(), Some(":history"));
+    assert_eq!(events[7].attempts, Some(2));
+
+    let mut output = Vec::new();
+    events::replay(&mut output, &events, 0.0).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("The secret was 42, between 1 and 100, with 10 attempts.\nSeed: 9\n"), "{}", output);
+    assert!(output.contains("s] You guessed: 50\n          Too big!\n"), "{}", output);
+    assert!(output.contains("s] :history\n"), "{}", output);
+    assert!(output.contains("Won in 2 attempts after "), "{}", output);
+
+    let mut output = Vec::new();
+    events::replay(&mut output, &[], 0.0).unwrap();
+    assert_eq!(output, b"The log is empty.\n");
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn huge_numbers_and_resumed_games_survive_the_log() {
+    let dir = temp_dir("huge");
+    let path = dir.join("game.jsonl");
+    let settings = Settings::new(Difficulty::Custom, i128::MIN, i128::MAX, None);
+    let mut game = Game::with_secret(settings, i128::MIN);
+    game.submit(i128::MAX);
+
+    let mut log = EventLog::create(&path).unwrap();
+    log.write(&Event::start(&game, u64::MAX)).unwrap();
+    log.write(&Event::quit(&game, "end_of_input")).unwrap();
+
+    let events = events::load(&path).unwrap();
+    assert_eq!(events[0].settings, Some(settings));
+    assert_eq!(events[0].secret, Some(i128::MIN));
+    assert_eq!(events[0].earlier, [i128::MAX]);
+    assert_eq!(events[0].seed, Some(u64::MAX));
+
+    let mut output = Vec::new();
+    events::replay(&mut output, &events, 0.0).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains(&format!("Resumed after guessing {}.", i128::MAX)), "{}", output);
+    assert!(output.contains("Left after 1 attempts and 0.0s (end of input)."), "{}", output);
+
+    fs::write(&path, "{\"time_ms\":0,\"event\":\"start\"}\nnot json\n").unwrap();
+    let error = events::load(&path).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    assert!(error.to_string().starts_with("line 2: "), "{}", error);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+// /dev/full opens fine and then fails every write with "no space left on device"
+#[cfg(target_os = "linux")]
+#[test]
+fn a_log_that_cannot_be_written_does_not_end_the_game() {
+    let session = logged_session(Path::new("/dev/full"));
+    let mut game = Game::with_secret(Settings::default(), 42);
+    let mut output = Vec::new();
+    let summary = play_game(Cursor::new("50\n42\n"), &mut output, &mut game, &session).unwrap();
+
+    assert_eq!(summary.status, Status::Won);
+    assert_eq!(summary.attempts, 2);
+    assert!(String::from_utf8(output).unwrap().contains("You win!"));
+}
+
+#[test]
+fn only_the_newest_sessions_are_kept_and_the_newest_is_replayed() {
+    let dir = temp_dir("keep");
+    let sessions = dir.join("sessions");
+    fs::create_dir_all(&sessions).unwrap();
+    for started in 1000..1000 + KEEP as u64 + 5 {
+        fs::write(sessions.join(format!("{}-1.jsonl", started)), "").unwrap();
+    }
+
+    let played = harness::run(&dir, &["--seed", "5"], ":quit\n");
+    assert_eq!(played.status.code(), Some(3));
+
+    let mut left: Vec<String> = fs::read_dir(&sessions)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    left.sort();
+    assert_eq!(left.len(), KEEP);
+    assert_eq!(left[0], format!("{}-1.jsonl", 1000 + 6));
+    assert!(left[KEEP - 1].ends_with("-5.jsonl"), "{:?}", left);
+
+    let replayed = harness::run(&dir, &["replay", "--speed", "0"], "");
+    let stdout = String::from_utf8_lossy(&replayed.stdout);
+    assert_eq!(replayed.status.code(), Some(0), "{}", stdout);
+    assert!(stdout.contains("Seed: 5\n"), "{}", stdout);
+    assert!(stdout.contains("(quit)."), "{}", stdout);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,91 @@
+use std::cmp::Ordering;
+use std::io::Cursor;
+use guessing_game::{play_game, Difficulty, Game, Outcome, Session, Settings, Status};
+
+fn settings(max_attempts: Option<u32>) -> Settings {
+    Settings::new(Difficulty::Custom, 1, 100, max_attempts)
+}
+
+#[test]
+fn guesses_are_compared_with_the_secret_and_counted() {
+    let mut game = Game::with_secret(settings(Some(10)), 42);
+
+    assert_eq!(game.submit(10), Outcome::Guess(Ordering::Less));
+    assert_eq!(game.submit(90), Outcome::Guess(Ordering::Greater));
+    assert_eq!(game.attempts(), 2);
+    assert_eq!(game.attempts_left(), Some(8));
+    assert_eq!(game.remaining(), (11, 89));
+    assert_eq!(game.status(), Status::Playing);
+
+    assert_eq!(game.submit(42), Outcome::Guess(Ordering::Equal));
+    assert_eq!(game.status(), Status::Won);
+    assert_eq!(game.attempts(), 3);
+    assert_eq!(game.history(), &[(10, Ordering::Less), (90, Ordering::Greater), (42, Ordering::Equal)]);
+
+    // once the game is over nothing more is counted
+    assert_eq!(game.submit(42), Outcome::Finished);
+    assert_eq!(game.attempts(), 3);
+}
+
+#[test]
+fn the_last_wrong_guess_loses() {
+    let mut game = Game::with_secret(settings(Some(2)), 42);
+
+    assert_eq!(game.submit(1), Outcome::Guess(Ordering::Less));
+    assert_eq!(game.status(), Status::Playing);
+    assert_eq!(game.submit(2), Outcome::Guess(Ordering::Less));
+    assert_eq!(game.status(), Status::Lost);
+    assert_eq!(game.attempts_left(), Some(0));
+    assert_eq!(game.submit(42), Outcome::Finished);
+}
+
+#[test]
+fn quitting_and_giving_up_only_end_an_undecided_game() {
+    let mut game = Game::with_secret(settings(None), 42);
+    assert_eq!(game.attempts_left(), None);
+    game.give_up();
+    assert_eq!(game.status(), Status::GaveUp);
+    game.quit();
+    assert_eq!(game.status(), Status::GaveUp);
+
+    let mut game = Game::with_secret(settings(None), 42);
+    game.submit(42);
+    game.quit();
+    assert_eq!(game.status(), Status::Won);
+}
+
+fn play(game: &mut Game, input: &str) -> (guessing_game::Summary, String) {
+    let mut output = Vec::new();
+    let summary = play_game(Cursor::new(input), &mut output, game, &Session::default()).unwrap();
+    (summary, String::from_utf8(output).unwrap())
+}
+
+#[test]
+fn a_game_is_played_from_any_reader_to_any_writer() {
+    let mut game = Game::with_secret(settings(Some(10)), 42);
+    let (summary, output) = play(&mut game, "50\nseven\n25\n42\n");
+
+    assert_eq!(summary.status, Status::Won);
+    assert_eq!(summary.attempts, 3);
+    assert_eq!(summary.secret, 42);
+    assert!(output.starts_with("Guess the number!\n"), "{}", output);
+    assert!(output.contains("You guessed: 50\nToo big!\n"), "{}", output);
+    assert!(output.contains("You guessed: 25\nToo small!\n"), "{}", output);
+    assert!(output.contains("You guessed: 42\nYou win!\n"), "{}", output);
+    // the line that wasn't a number is not counted
+    assert_eq!(game.history().len(), 3);
+}
+
+#[test]
+fn running_out_of_input_or_attempts_ends_the_game() {
+    let mut game = Game::with_secret(settings(Some(10)), 42);
+    let (summary, output) = play(&mut game, "50\n");
+    assert_eq!(summary.status, Status::Quit);
+    assert_eq!(summary.attempts, 1);
+    assert!(output.contains("42"), "{}", output);
+
+    let mut game = Game::with_secret(settings(Some(2)), 42);
+    let (summary, _) = play(&mut game, "1\n2\n3\n");
+    assert_eq!(summary.status, Status::Lost);
+    assert_eq!(summary.attempts, 2);
+}
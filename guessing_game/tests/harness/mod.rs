@@ -0,0 +1,261 @@
+// drives the guessing_game binary the way a player at a terminal would: send a line, then expect
+// the text that should come back. a transcript file plays a whole session from a script.
+//
+// a transcript looks like this:
+//
+//     # comments start with a hash
+//     $ --seed 42
+//     Seed: 42
+//     Please input your guess.
+//     > 50
+//     >
+//     You guessed: 50
+//     <eof>
+//     [exit 3]
+//
+// `$` gives the arguments, `>` sends a line (a bare `>` sends an empty one), `<eof>` closes stdin and `[exit N]` checks that the
+// output ends there with exit code N. every other line must match the next line of output exactly,
+// except that `{..}` stands for any run of text, for times and dates that change from run to run.
+//
+// every test that runs a binary starts it through here, so none of them can pick up the machine's
+// own config.toml, data, locale, NO_COLOR or seed.
+
+// each test binary uses only some of this
+#![allow(dead_code)]
+
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, Command, Output, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+// how long to wait for a line before deciding the game is stuck
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+// each caller gets its own directory, so tests can run in parallel and start from nothing
+static DIRS: AtomicUsize = AtomicUsize::new(0);
+
+// a fresh, empty directory under the system's temp dir
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "guessing_game-{}-{}-{}",
+        name,
+        std::process::id(),
+        DIRS.fetch_add(1, Ordering::SeqCst)
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+// `program` with the environment pinned down: its data goes straight into dir, its config comes
+// from dir/config, and the player is called tester
+fn pinned(program: &str, dir: &Path, args: &[&str]) -> Command {
+    let mut command = Command::new(program);
+    command
+        .args(args)
+        .env("GUESSING_GAME_DATA", dir)
+        .env("XDG_CONFIG_HOME", dir.join("config"))
+        .env("USER", "tester")
+        .env_remove("LC_ALL")
+        .env_remove("LC_MESSAGES")
+        .env_remove("LANG")
+        .env_remove("NO_COLOR")
+        .env_remove("GUESSING_GAME_SEED");
+    command
+}
+
+// guessing_game with `args`, keeping its files in dir
+pub fn command(dir: &Path, args: &[&str]) -> Command {
+    pinned(env!("CARGO_BIN_EXE_guessing_game"), dir, args)
+}
+
+// the multiplayer client with `args`, keeping its files in dir
+pub fn client(dir: &Path, args: &[&str]) -> Command {
+    pinned(env!("CARGO_BIN_EXE_guessing_client"), dir, args)
+}
+
+// runs a command to the end with `input` on stdin, and collects what it printed
+pub fn run_with(mut command: Command, input: &str) -> Output {
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to start guessing_game");
+    child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+// runs guessing_game to the end with `args`, keeping its files in dir
+pub fn run(dir: &Path, args: &[&str], input: &str) -> Output {
+    run_with(command(dir, args), input)
+}
+
+pub struct Session {
+    child: Child,
+    stdin: Option<ChildStdin>,
+    lines: Receiver<String>,
+    // everything sent and received so far, shown when an expectation fails
+    transcript: Vec<String>,
+    dir: PathBuf,
+}
+
+impl Session {
+    // starts the binary with a fresh data directory and none of the machine's own settings
+    pub fn spawn(args: &[&str]) -> Session {
+        let dir = temp_dir("e2e");
+        let mut child = command(&dir, args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("failed to start guessing_game");
+
+        // a thread does the blocking reads, so a game that goes quiet fails the test instead of hanging it
+        let stdout = BufReader::new(child.stdout.take().unwrap());
+        let (sender, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in stdout.lines().map_while(Result::ok) {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Session {
+            stdin: child.stdin.take(),
+            child,
+            lines,
+            transcript: vec![format!("$ {}", args.join(" "))],
+            dir,
+        }
+    }
+
+    fn fail(&self, message: &str) -> ! {
+        panic!("{}\n--- transcript so far ---\n{}", message, self.transcript.join("\n"));
+    }
+
+    pub fn send(&mut self, line: &str) {
+        self.transcript.push(format!("> {}", line));
+        let stdin = match self.stdin.as_mut() {
+            Some(stdin) => stdin,
+            None => self.fail("sent a line after closing stdin"),
+        };
+        if writeln!(stdin, "{}", line).is_err() {
+            self.fail(&format!("the game stopped reading before {:?}", line));
+        }
+    }
+
+    // what a player gets by pressing Ctrl-D
+    pub fn close_stdin(&mut self) {
+        self.transcript.push(String::from("<eof>"));
+        self.stdin = None;
+    }
+
+    // the next line of output, or None once the game has closed stdout
+    pub fn next_line(&mut self) -> Option<String> {
+        match self.lines.recv_timeout(TIMEOUT) {
+            Ok(line) => {
+                self.transcript.push(line.clone());
+                Some(line)
+            }
+            Err(RecvTimeoutError::Disconnected) => None,
+            Err(RecvTimeoutError::Timeout) => self.fail("timed out waiting for output"),
+        }
+    }
+
+    // the next line of output has to match `expected`, {..} and all
+    pub fn expect(&mut self, expected: &str) {
+        match self.next_line() {
+            Some(line) if matches(expected, &line) => {}
+            Some(line) => self.fail(&format!("expected {:?}\n     got {:?}", expected, line)),
+            None => self.fail(&format!("expected {:?}, but the output ended", expected)),
+        }
+    }
+
+    // skips output until a line matches, and returns it
+    pub fn expect_eventually(&mut self, expected: &str) -> String {
+        loop {
+            match self.next_line() {
+                Some(line) if matches(expected, &line) => return line,
+                Some(_) => {}
+                None => self.fail(&format!("the output ended without {:?}", expected)),
+            }
+        }
+    }
+
+    // the output has to end here, and the game exit with `code`
+    pub fn expect_exit(mut self, code: i32) {
+        self.stdin = None;
+        if let Some(line) = self.next_line() {
+            self.fail(&format!("expected the output to end, got {:?}", line));
+        }
+        let status = self.child.wait().expect("failed to wait for guessing_game");
+        if status.code() != Some(code) {
+            self.fail(&format!("expected exit code {}, got {:?}", code, status.code()));
+        }
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+// `expected` with every {..} allowed to match any text
+pub fn matches(expected: &str, line: &str) -> bool {
+    let mut parts = expected.split("{..}");
+    let first = parts.next().unwrap_or("");
+    let mut rest = match line.strip_prefix(first) {
+        Some(rest) => rest,
+        None => return false,
+    };
+    let mut parts: Vec<&str> = parts.collect();
+    let last = match parts.pop() {
+        Some(last) => last,
+        // no wildcard at all
+        None => return rest.is_empty(),
+    };
+    for part in parts {
+        match rest.find(part) {
+            Some(at) => rest = &rest[at + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+// plays the transcript at tests/transcripts/<name>.txt
+pub fn replay(name: &str) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/transcripts").join(format!("{}.txt", name));
+    let script = fs::read_to_string(&path).unwrap_or_else(|e| panic!("could not read {}: {}", path.display(), e));
+
+    let mut lines = script.lines().filter(|line| !line.starts_with('#'));
+    let args = match lines.next().and_then(|line| line.strip_prefix("$")) {
+        Some(args) => args.split_whitespace().collect::<Vec<_>>(),
+        None => panic!("{} has to start with a `$` line of arguments", path.display()),
+    };
+
+    let mut session = Session::spawn(&args);
+    for line in lines {
+        if let Some(sent) = line.strip_prefix("> ") {
+            session.send(sent);
+        } else if line == ">" {
+            session.send("");
+        } else if line == "<eof>" {
+            session.close_stdin();
+        } else if let Some(code) = line.strip_prefix("[exit ").and_then(|rest| rest.strip_suffix(']')) {
+            let code = code.parse().unwrap_or_else(|_| panic!("bad exit line in {}: {}", path.display(), line));
+            return session.expect_exit(code);
+        } else {
+            session.expect(line);
+        }
+    }
+    panic!("{} has to end with an [exit N] line", path.display());
+}
@@ -0,0 +1,113 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use guessing_game::hotseat::{hot_seat, validate, Standing};
+use guessing_game::locale::Locale;
+use guessing_game::Settings;
+
+const SEED: u64 = 11;
+
+fn players() -> Vec<String> {
+    vec![String::from("alice"), String::from("bob")]
+}
+
+// the shared secret of each round, drawn from the rng the same way the match draws them
+fn secrets(rounds: u32) -> Vec<i128> {
+    let mut rng = StdRng::seed_from_u64(SEED);
+    let settings = Settings::default();
+    (0..rounds).map(|_| rng.gen_range(settings.low..=settings.high)).collect()
+}
+
+// a guess that is sure to miss
+fn miss(secret: i128) -> i128 {
+    if secret == 1 { 2 } else { secret - 1 }
+}
+
+fn play(rounds: u32, lines: &[String]) -> (Option<Vec<Standing>>, String) {
+    let mut output = Vec::new();
+    let standings = hot_seat(
+        lines.join("\n").as_bytes(),
+        &mut output,
+        Settings::default(),
+        &players(),
+        true,
+        rounds,
+        &mut StdRng::seed_from_u64(SEED),
+        Locale::En,
+    )
+    .unwrap();
+    (standings, String::from_utf8(output).unwrap())
+}
+
+fn scores(standings: &[Standing]) -> Vec<(&str, u32, u32)> {
+    standings.iter().map(|s| (s.name.as_str(), s.score, s.attempts)).collect()
+}
+
+#[test]
+fn a_match_is_won_on_rounds_and_stops_once_the_lead_cannot_be_caught() {
+    // alice finds every secret at once and bob always misses, so best of 3 is over after round 2
+    let secrets = secrets(3);
+    let lines: Vec<String> = secrets
+        .iter()
+        .flat_map(|&secret| [secret.to_string(), miss(secret).to_string()])
+        .collect();
+    let (standings, output) = play(3, &lines);
+
+    assert_eq!(scores(&standings.unwrap()), [("alice", 2, 2), ("bob", 0, 2)]);
+    assert!(output.contains("alice wins round 1!"), "{}", output);
+    assert!(output.contains("Score: alice 2, bob 0"), "{}", output);
+    assert!(!output.contains("Round 3"), "{}", output);
+    assert!(output.contains("alice wins the match!"), "{}", output);
+}
+
+#[test]
+fn the_match_goes_on_while_the_lead_can_still_be_caught() {
+    let secrets = secrets(3);
+    let lines: Vec<String> = vec![
+        // alice takes round 1, bob round 2 and then both round 3
+        secrets[0].to_string(),
+        miss(secrets[0]).to_string(),
+        miss(secrets[1]).to_string(),
+        secrets[1].to_string(),
+        secrets[2].to_string(),
+        secrets[2].to_string(),
+    ];
+    let (standings, output) = play(3, &lines);
+
+    assert_eq!(scores(&standings.unwrap()), [("alice", 2, 3), ("bob", 2, 3)]);
+    assert!(output.contains("bob wins round 2!"), "{}", output);
+    assert!(output.contains("Round 3 is a tie between alice and bob!"), "{}", output);
+    assert!(output.contains("The match is a tie between alice and bob."), "{}", output);
+}
+
+#[test]
+fn everyone_gets_the_same_number_of_turns_in_a_lap() {
+    // alice misses, then both guess on the second lap: the round is a tie, not a win for whoever went first
+    let secret = secrets(1)[0];
+    let lines = [miss(secret), miss(secret), secret, secret].map(|guess| guess.to_string());
+    let (standings, output) = play(1, &lines);
+
+    assert_eq!(scores(&standings.unwrap()), [("alice", 1, 2), ("bob", 1, 2)]);
+    assert!(output.contains("alice, your guess (attempt 2):"), "{}", output);
+    assert!(output.contains("Round 1 is a tie between alice and bob!"), "{}", output);
+}
+
+#[test]
+fn giving_up_leaves_the_round_to_the_others_and_quitting_ends_the_match() {
+    let secret = secrets(1)[0];
+    let lines = [String::from(":giveup"), miss(secret).to_string(), secret.to_string()];
+    let (standings, output) = play(1, &lines);
+    assert_eq!(scores(&standings.unwrap()), [("alice", 0, 0), ("bob", 1, 2)]);
+    assert!(output.contains("alice gives up this round."), "{}", output);
+
+    let (standings, output) = play(3, &[miss(secret).to_string(), String::from(":quit")]);
+    assert_eq!(standings, None);
+    assert!(output.ends_with("Match abandoned.\n"), "{}", output);
+}
+
+#[test]
+fn matches_need_enough_players_and_rounds() {
+    assert!(validate(&players(), 1).is_ok());
+    assert!(validate(&players()[..1], 1).unwrap_err().contains("between 2 and 8 players, got 1"));
+    assert!(validate(&vec![String::from("x"); 9], 1).is_err());
+    assert_eq!(validate(&players(), 0), Err(String::from("a match needs at least one round")));
+}
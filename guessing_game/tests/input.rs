@@ -0,0 +1,69 @@
+use std::io::Cursor;
+use guessing_game::input::{parse_guess, InvalidGuess};
+use guessing_game::{play_game, Difficulty, Game, Session, Settings, Status};
+
+#[test]
+fn every_kind_of_bad_line_is_told_apart() {
+    let settings = Settings::default();
+
+    assert_eq!(parse_guess(" 42 \n", &settings), Ok(42));
+    assert_eq!(parse_guess("+7", &settings), Ok(7));
+    assert_eq!(parse_guess("\n", &settings), Err(InvalidGuess::Empty));
+    assert_eq!(parse_guess("4 2", &settings), Err(InvalidGuess::NotANumber(String::from("4 2"))));
+    assert_eq!(parse_guess("1.5", &settings), Err(InvalidGuess::NotANumber(String::from("1.5"))));
+    assert_eq!(parse_guess("-3", &settings), Err(InvalidGuess::Negative(-3)));
+    assert_eq!(parse_guess(&"9".repeat(40), &settings), Err(InvalidGuess::Overflow));
+    assert_eq!(parse_guess(&format!("-{}", "9".repeat(40)), &settings), Err(InvalidGuess::Overflow));
+    assert_eq!(
+        parse_guess("101", &settings),
+        Err(InvalidGuess::OutOfRange { guess: 101, low: 1, high: 100 })
+    );
+
+    // a negative guess is only "negative" when the range has no negative numbers
+    let signed = Settings::new(Difficulty::Custom, -10, 10, None);
+    assert_eq!(parse_guess("-3", &signed), Ok(-3));
+    assert_eq!(
+        parse_guess("-11", &signed),
+        Err(InvalidGuess::OutOfRange { guess: -11, low: -10, high: 10 })
+    );
+}
+
+#[test]
+fn the_player_is_told_why_a_guess_was_refused() {
+    assert_eq!(InvalidGuess::Empty.to_string(), "Please type a number.");
+    assert_eq!(
+        InvalidGuess::OutOfRange { guess: 101, low: 1, high: 100 }.to_string(),
+        "101 is out of range; guess between 1 and 100."
+    );
+
+    let mut game = Game::with_secret(Settings::default(), 42);
+    let mut output = Vec::new();
+    let summary = play_game(Cursor::new("\nabc\n-3\n500\n42\n"), &mut output, &mut game, &Session::default()).unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    // none of the bad lines cost an attempt
+    assert_eq!(summary.status, Status::Won);
+    assert_eq!(summary.attempts, 1);
+    for invalid in [
+        InvalidGuess::Empty,
+        InvalidGuess::NotANumber(String::from("abc")),
+        InvalidGuess::Negative(-3),
+        InvalidGuess::OutOfRange { guess: 500, low: 1, high: 100 },
+    ] {
+        assert!(output.contains(&invalid.to_string()), "no {:?} in:\n{}", invalid, output);
+    }
+}
+
+#[test]
+fn end_of_input_leaves_the_game_and_shows_the_secret() {
+    for input in ["", "50\n", "50"] {
+        let mut game = Game::with_secret(Settings::default(), 42);
+        let mut output = Vec::new();
+        let summary = play_game(Cursor::new(input), &mut output, &mut game, &Session::default()).unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert_eq!(summary.status, Status::Quit, "{:?}", input);
+        assert_eq!(summary.status.exit_code(), 3);
+        assert!(output.trim_end().ends_with("42."), "{:?} gave:\n{}", input, output);
+    }
+}
@@ -0,0 +1,74 @@
+use std::fs;
+use guessing_game::leaderboard::{Entry, Variant};
+use guessing_game::{Difficulty, Leaderboard};
+
+fn entry(player: &str, difficulty: Difficulty, attempts: u32, duration_ms: u64, timestamp: u64) -> Entry {
+    Entry {
+        player: player.to_string(),
+        variant: Variant::Number,
+        difficulty,
+        low: 1,
+        high: 100,
+        attempts,
+        duration_ms,
+        timestamp,
+    }
+}
+
+#[test]
+fn fewest_attempts_then_fastest_then_earliest_come_first() {
+    let mut board = Leaderboard::default();
+    board.record(entry("slow", Difficulty::Normal, 5, 9000, 1));
+    board.record(entry("late", Difficulty::Normal, 5, 1000, 3));
+    board.record(entry("fast", Difficulty::Normal, 5, 1000, 2));
+    board.record(entry("lucky", Difficulty::Normal, 1, 50000, 4));
+    board.record(entry("easy", Difficulty::Easy, 1, 10, 5));
+
+    let names = |entries: Vec<&Entry>| entries.iter().map(|entry| entry.player.clone()).collect::<Vec<_>>();
+    assert_eq!(names(board.top(Variant::Number, Difficulty::Normal, 10)), ["lucky", "fast", "late", "slow"]);
+    assert_eq!(names(board.top(Variant::Number, Difficulty::Normal, 2)), ["lucky", "fast"]);
+    assert_eq!(names(board.top(Variant::Number, Difficulty::Easy, 10)), ["easy"]);
+    assert!(board.top(Variant::BullsAndCows, Difficulty::Normal, 10).is_empty());
+    assert!(!board.has_entries(Variant::BullsAndCows));
+}
+
+#[test]
+fn the_board_survives_a_save_and_a_load() {
+    let dir = std::env::temp_dir().join(format!("guessing_game-leaderboard-{}", std::process::id()));
+    let path = dir.join("nested").join("leaderboard.json");
+
+    // nothing saved yet is an empty board
+    assert!(!Leaderboard::load(&path).unwrap().has_entries(Variant::Number));
+
+    let mut board = Leaderboard::default();
+    board.record(entry("ana", Difficulty::Hard, 9, 1234, 1_700_000_000));
+    board.save(&path).unwrap();
+    let loaded = Leaderboard::load(&path).unwrap();
+    assert_eq!(loaded.top(Variant::Number, Difficulty::Hard, 10), board.top(Variant::Number, Difficulty::Hard, 10));
+
+    // entries written before bulls and cows existed have no variant and count as number games
+    let old = r#"{"entries":[{"player":"bo","difficulty":"easy","low":1,"high":50,"attempts":4,"duration_ms":10,"timestamp":0}]}"#;
+    fs::write(&path, old).unwrap();
+    let loaded = Leaderboard::load(&path).unwrap();
+    assert_eq!(loaded.top(Variant::Number, Difficulty::Easy, 10)[0].player, "bo");
+
+    fs::write(&path, "not json").unwrap();
+    assert!(Leaderboard::load(&path).is_err());
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn the_top_entries_are_printed_as_a_table() {
+    let mut board = Leaderboard::default();
+    let mut output = Vec::new();
+    board.print_top(&mut output, Variant::Number, Difficulty::Normal).unwrap();
+    assert_eq!(String::from_utf8(output).unwrap(), "Top 10 (normal):\n  no wins yet\n");
+
+    board.record(entry("ana", Difficulty::Normal, 7, 2500, 0));
+    let mut output = Vec::new();
+    board.print_top(&mut output, Variant::Number, Difficulty::Normal).unwrap();
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "Top 10 (normal):\n  1. ana                7 attempts      2.5s  1..=100  1970-01-01\n"
+    );
+}
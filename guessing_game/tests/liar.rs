@@ -0,0 +1,172 @@
+use std::cmp::Ordering;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use guessing_game::liar::{liar_game, min_questions, solve_game, validate_solver, volume, Oracle, Solver};
+use guessing_game::locale::Locale;
+use guessing_game::{Difficulty, Settings, Status};
+
+#[test]
+fn the_volume_bound_grows_with_the_lies() {
+    assert_eq!(volume(10, 0), 1);
+    assert_eq!(volume(10, 1), 11);
+    assert_eq!(volume(10, 2), 56);
+    assert_eq!(volume(3, 5), 8);
+
+    // with no lies it is binary search, which has narrowed 100 numbers down to one after 6 questions
+    assert_eq!(min_questions(100, 0), 6);
+    assert_eq!(min_questions(127, 0), 6);
+    assert_eq!(min_questions(128, 0), 7);
+    assert_eq!(min_questions(1, 3), 0);
+    for lies in 0..4 {
+        assert!(min_questions(100, lies + 1) > min_questions(100, lies));
+    }
+}
+
+#[test]
+fn the_oracle_never_lies_more_than_allowed_or_about_a_win() {
+    let mut rng = StdRng::seed_from_u64(3);
+    let mut oracle = Oracle::new(50, 2);
+    let mut lies = 0;
+    for _ in 0..100 {
+        if oracle.reply(10, &mut rng) != Ordering::Less {
+            lies += 1;
+        }
+        assert_eq!(oracle.reply(50, &mut rng), Ordering::Equal);
+    }
+    assert_eq!(lies, 2);
+    assert_eq!(oracle.lied_on().len(), 2);
+}
+
+// plays the solver against a liar that always gives whichever reply keeps the most candidates
+// alive, lying whenever that helps and it still may. returns the number the solver settled on and
+// how many questions it asked.
+fn against_an_adversary(settings: &Settings, secret: i128, lies: u32) -> (i128, u32) {
+    let mut solver = Solver::new(settings, lies);
+    let mut lies_left = lies;
+    loop {
+        if let Some(found) = solver.identified() {
+            return (found, solver.questions());
+        }
+        assert!(solver.questions() < 64, "no answer for {} after 64 questions", secret);
+
+        let guess = solver.next_guess();
+        let truth = guess.cmp(&secret);
+        let mut reply = truth;
+        if truth != Ordering::Equal && lies_left > 0 {
+            let left_with = |reply: Ordering| {
+                let mut solver = solver.clone();
+                solver.record(guess, reply);
+                solver.candidates().len()
+            };
+            if left_with(truth.reverse()) >= left_with(truth) {
+                reply = truth.reverse();
+                lies_left -= 1;
+            }
+        }
+        solver.record(guess, reply);
+        if reply == Ordering::Equal {
+            return (guess, solver.questions());
+        }
+        assert!(solver.candidates().contains(&secret), "{} was ruled out", secret);
+    }
+}
+
+#[test]
+fn the_solver_finds_every_secret_against_an_adversarial_liar() {
+    for (high, lies) in [(16, 1), (50, 1), (20, 2), (12, 3)] {
+        let settings = Settings::new(Difficulty::Custom, 1, high, None);
+        let needed = Solver::new(&settings, lies).questions_needed();
+        assert!(needed >= min_questions(high as u128, lies), "1..={} with {} lies", high, lies);
+        for secret in 1..=high {
+            let (found, questions) = against_an_adversary(&settings, secret, lies);
+            assert_eq!(found, secret, "1..={} with {} lies", high, lies);
+            assert!(questions <= needed, "1..={} with {} lies took {} questions for {}", high, lies, questions, secret);
+        }
+    }
+}
+
+// a liar with no secret in mind: it answers whatever leaves the most questions to go, so the
+// solver gets no easier game than its worst case
+#[test]
+fn the_solver_needs_exactly_the_questions_it_reports_against_the_worst_replies() {
+    for (high, lies) in [(100, 0), (30, 1), (16, 2)] {
+        let settings = Settings::new(Difficulty::Custom, 1, high, None);
+        let mut solver = Solver::new(&settings, lies);
+        let needed = solver.questions_needed();
+        while solver.identified().is_none() {
+            let guess = solver.next_guess();
+            let after = |reply: Ordering| {
+                let mut solver = solver.clone();
+                solver.record(guess, reply);
+                (!solver.candidates().is_empty()).then(|| solver.questions_needed())
+            };
+            let reply = [Ordering::Less, Ordering::Greater]
+                .into_iter()
+                .max_by_key(|&reply| after(reply))
+                .unwrap();
+            solver.record(guess, reply);
+            assert_eq!(solver.questions() + solver.questions_needed(), needed, "1..={} with {} lies", high, lies);
+        }
+        assert_eq!(solver.questions(), needed, "1..={} with {} lies", high, lies);
+    }
+}
+
+#[test]
+fn the_solver_finds_the_secret_within_the_questions_it_reports() {
+    for (high, lies) in [(100, 0), (100, 1), (20, 2)] {
+        let settings = Settings::new(Difficulty::Custom, 1, high, None);
+        for seed in 0..30 {
+            let mut output = Vec::new();
+            let mut rng = StdRng::seed_from_u64(seed);
+            let questions = solve_game(&mut output, settings, lies, &mut rng, Locale::En).unwrap();
+            let output = String::from_utf8(output).unwrap();
+
+            let asked: Vec<&str> = output.lines().filter(|line| line.starts_with("Is it ")).collect();
+            assert_eq!(asked.len() as u32, questions, "{}", output);
+
+            // every reply that doesn't fit the number it names must be one of the lies it owns up to
+            let last = output.lines().last().unwrap();
+            let found: i128 = last["The number is ".len()..].split(',').next().unwrap().parse().unwrap();
+            let mut wrong = Vec::new();
+            for (i, line) in asked.iter().enumerate() {
+                let (guess, reply) = line["Is it ".len()..].split_once("? ").unwrap();
+                let truth = match guess.parse::<i128>().unwrap().cmp(&found) {
+                    Ordering::Less => "Too small!",
+                    Ordering::Greater => "Too big!",
+                    Ordering::Equal => "You win!",
+                };
+                if reply != truth {
+                    wrong.push(format!("#{}", i + 1));
+                }
+            }
+            assert!(wrong.len() as u32 <= lies, "{}", output);
+            if wrong.is_empty() {
+                assert!(last.ends_with("I never lied."), "{}", output);
+            } else {
+                assert!(last.ends_with(&format!("I lied on answers {}.", wrong.join(", "))), "{}", output);
+            }
+        }
+    }
+}
+
+#[test]
+fn the_extra_attempts_for_lies_stop_at_the_largest_limit() {
+    let settings = Settings::new(Difficulty::Custom, 1, 100, Some(u32::MAX));
+    let mut output = Vec::new();
+    let mut rng = StdRng::seed_from_u64(1);
+    let summary = liar_game(":giveup\n".as_bytes(), &mut output, settings, 1, &mut rng, Locale::En).unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    assert_eq!(summary.status, Status::GaveUp);
+    assert!(output.contains(&format!("You have {} attempts.", u32::MAX)), "{}", output);
+}
+
+#[test]
+fn the_solver_only_takes_on_games_it_can_finish() {
+    let settings = |high| Settings::new(Difficulty::Custom, 1, high, None);
+    assert!(validate_solver(&settings(100), 1).is_ok());
+    assert!(validate_solver(&settings(40), 2).is_ok());
+    assert!(validate_solver(&settings(41), 2).is_err());
+    assert!(validate_solver(&settings(101), 0).is_err());
+    assert!(validate_solver(&settings(5), 4).is_err());
+}
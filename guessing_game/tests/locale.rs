@@ -0,0 +1,123 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+use guessing_game::locale::Locale;
+
+// the {name} placeholders in a message
+fn placeholders(text: &str) -> BTreeSet<&str> {
+    text.split('{')
+        .skip(1)
+        .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+        .collect()
+}
+
+#[test]
+fn every_locale_defines_every_key() {
+    for locale in Locale::ALL {
+        for (key, english) in Locale::En.catalog() {
+            let text = locale
+                .lookup(key)
+                .unwrap_or_else(|| panic!("{} has no message for {}", locale, key));
+            assert!(!text.trim().is_empty(), "{} has an empty message for {}", locale, key);
+            assert_eq!(
+                placeholders(text),
+                placeholders(english),
+                "{} fills in different values for {}",
+                locale,
+                key
+            );
+        }
+    }
+}
+
+#[test]
+fn no_locale_has_keys_english_lacks_or_repeats_one() {
+    for locale in Locale::ALL {
+        let mut seen = BTreeSet::new();
+        for (key, _) in locale.catalog() {
+            assert!(Locale::En.lookup(key).is_some(), "{} has a stray key {}", locale, key);
+            assert!(seen.insert(key), "{} defines {} twice", locale, key);
+        }
+    }
+}
+
+// the string literals in a call's first argument, which runs up to the first `,` or `)` outside
+// brackets and strings. the argument is the key, or an `if` picking between keys.
+fn first_argument_literals(call: &str) -> Vec<String> {
+    let mut literals = Vec::new();
+    let mut depth = 0;
+    let mut chars = call.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                let mut literal = String::new();
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\\' => literal.extend(chars.next()),
+                        '"' => break,
+                        c => literal.push(c),
+                    }
+                }
+                literals.push(literal);
+            }
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' if depth > 0 => depth -= 1,
+            ',' | ')' if depth == 0 => break,
+            _ => {}
+        }
+    }
+    literals
+}
+
+// every key passed to text or format anywhere in the source, with the file it's used in
+fn used_keys(dir: &Path, keys: &mut Vec<(String, String)>) {
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            used_keys(&path, keys);
+            continue;
+        }
+        let source = fs::read_to_string(&path).unwrap();
+        for call in [".text(", ".format("] {
+            for (at, _) in source.match_indices(call) {
+                let argument = &source[at + call.len()..];
+                let literals = first_argument_literals(argument);
+                // Locale::format passes its own key along to text
+                if literals.is_empty() && argument.starts_with("key)") {
+                    continue;
+                }
+                assert!(!literals.is_empty(), "{}: a key that isn't written out: {}", path.display(), call);
+                keys.extend(literals.into_iter().map(|key| (path.display().to_string(), key)));
+            }
+        }
+    }
+}
+
+#[test]
+fn every_key_the_code_uses_has_a_message_in_every_locale() {
+    let mut keys = Vec::new();
+    used_keys(Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/src")), &mut keys);
+    // a scan that finds next to nothing has stopped seeing the calls
+    assert!(keys.len() > 40, "only found {:?}", keys);
+
+    for (file, key) in &keys {
+        for locale in Locale::ALL {
+            assert!(locale.lookup(key).is_some(), "{} uses {}, which {} has no message for", file, key, locale);
+        }
+    }
+}
+
+#[test]
+fn lang_values_pick_a_locale() {
+    assert_eq!("de_DE.UTF-8".parse(), Ok(Locale::De));
+    assert_eq!("es-MX".parse(), Ok(Locale::Es));
+    assert_eq!("EN".parse(), Ok(Locale::En));
+    assert!("C.UTF-8".parse::<Locale>().is_err());
+    assert!("fr_FR".parse::<Locale>().is_err());
+}
+
+#[test]
+fn values_are_filled_in() {
+    let text = Locale::De.format("hint", &[("low", &3), ("high", &9)]);
+    assert_eq!(text, "Hinweis: Die geheime Zahl liegt zwischen 3 und 9.");
+}
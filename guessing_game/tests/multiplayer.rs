@@ -0,0 +1,208 @@
+mod harness;
+
+use std::cmp::Ordering;
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::path::PathBuf;
+use std::process::{Child, Stdio};
+use guessing_game::multiplayer::{ClientMessage, ServerMessage};
+
+// kills the server even when an assertion fails part way through, and clears away its directory
+struct Server(Child, PathBuf);
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+        let _ = fs::remove_dir_all(&self.1);
+    }
+}
+
+// starts `guessing_game serve` on a free port and returns it with its address
+fn start_server() -> (Server, String) {
+    let dir = harness::temp_dir("multiplayer");
+    let mut child = harness::command(&dir, &["serve", "--port", "0", "--seed", "7"])
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to start the server");
+
+    let mut stdout = BufReader::new(child.stdout.take().unwrap());
+    let mut line = String::new();
+    let address = loop {
+        line.clear();
+        assert!(stdout.read_line(&mut line).unwrap() > 0, "server exited early");
+        if let Some(address) = line.trim().strip_prefix("Listening on ") {
+            break address.to_string();
+        }
+    };
+
+    (Server(child, dir), address)
+}
+
+// one write per line, so small messages aren't held back waiting for an acknowledgement
+fn say(writer: &mut TcpStream, message: ClientMessage) {
+    writer.write_all(format!("{}\n", message).as_bytes()).unwrap();
+}
+
+fn next_message<R: BufRead>(reader: &mut R) -> ServerMessage {
+    let mut line = String::new();
+    assert!(reader.read_line(&mut line).unwrap() > 0, "server hung up");
+    ServerMessage::parse(&line).unwrap()
+}
+
+#[test]
+fn players_race_for_the_same_secret_over_localhost() {
+    let (_server, address) = start_server();
+
+    // alice speaks the protocol directly so the test can follow every message
+    let alice = TcpStream::connect(&address).unwrap();
+    let mut alice_reader = BufReader::new(alice.try_clone().unwrap());
+    let mut alice_writer = alice;
+    writeln!(alice_writer, "{}", ClientMessage::Join {
+        room: String::from("race"),
+        name: String::from("alice"),
+    })
+    .unwrap();
+
+    let (mut low, mut high) = match next_message(&mut alice_reader) {
+        ServerMessage::Welcome { room, low, high } => {
+            assert_eq!(room, "race");
+            (low, high)
+        }
+        other => panic!("expected a welcome, got {:?}", other),
+    };
+
+    // bob uses the client binary, fed a single out of range guess
+    let bob_dir = harness::temp_dir("multiplayer-client");
+    let mut bob = harness::client(&bob_dir, &["--addr", &address, "--room", "race", "--name", "bob"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to start the client");
+    assert_eq!(
+        next_message(&mut alice_reader),
+        ServerMessage::Joined { name: String::from("bob") }
+    );
+
+    // alice binary searches, and every guess comes back to her as a broadcast
+    let mut attempts = 0;
+    let secret = loop {
+        let guess = low + (high - low) / 2;
+        writeln!(alice_writer, "{}", ClientMessage::Guess(guess.to_string())).unwrap();
+        attempts += 1;
+
+        match next_message(&mut alice_reader) {
+            ServerMessage::Guess { name, guess: echoed, ordering } => {
+                assert_eq!(name, "alice");
+                assert_eq!(echoed, guess);
+                match ordering {
+                    Ordering::Less => low = guess + 1,
+                    Ordering::Greater => high = guess - 1,
+                    Ordering::Equal => break guess,
+                }
+            }
+            other => panic!("expected a guess result, got {:?}", other),
+        }
+    };
+
+    assert_eq!(
+        next_message(&mut alice_reader),
+        ServerMessage::Winner {
+            name: String::from("alice"),
+            secret,
+            attempts,
+        }
+    );
+    assert!(matches!(next_message(&mut alice_reader), ServerMessage::NewRound { .. }));
+
+    // bob sends one bad guess and leaves
+    bob.stdin.take().unwrap().write_all(b"1000\n:quit\n").unwrap();
+    let mut transcript = String::new();
+    bob.stdout.take().unwrap().read_to_string(&mut transcript).unwrap();
+    assert!(bob.wait().unwrap().success());
+    let _ = fs::remove_dir_all(&bob_dir);
+
+    assert_eq!(
+        next_message(&mut alice_reader),
+        ServerMessage::Left { name: String::from("bob") }
+    );
+
+    assert!(transcript.contains("Welcome to room race!"), "{}", transcript);
+    assert!(transcript.contains(&format!("alice guessed {}: You win!", secret)), "{}", transcript);
+    assert!(
+        transcript.contains(&format!("alice wins! The secret number was {}", secret)),
+        "{}",
+        transcript
+    );
+    assert!(transcript.contains("New round!"), "{}", transcript);
+    assert!(transcript.contains("Error: 1000 is out of range"), "{}", transcript);
+}
+
+// connects, joins a room and reads the welcome, returning the two halves of the connection
+fn join(address: &str, room: &str, name: &str) -> (BufReader<TcpStream>, TcpStream) {
+    let stream = TcpStream::connect(address).unwrap();
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut writer = stream;
+    say(&mut writer, ClientMessage::Join {
+        room: room.to_string(),
+        name: name.to_string(),
+    });
+    assert!(matches!(next_message(&mut reader), ServerMessage::Welcome { room: joined, .. } if joined == room));
+    (reader, writer)
+}
+
+#[test]
+fn players_who_quit_or_hang_up_are_announced_as_leaving() {
+    let (_server, address) = start_server();
+    let (mut alice, _alice_writer) = join(&address, "lobby", "alice");
+
+    let (_, mut bob) = join(&address, "lobby", "bob");
+    assert_eq!(next_message(&mut alice), ServerMessage::Joined { name: String::from("bob") });
+    say(&mut bob, ClientMessage::Quit);
+    assert_eq!(next_message(&mut alice), ServerMessage::Left { name: String::from("bob") });
+
+    // carol just goes away without a word
+    let (carol_reader, carol) = join(&address, "lobby", "carol");
+    assert_eq!(next_message(&mut alice), ServerMessage::Joined { name: String::from("carol") });
+    drop(carol_reader);
+    drop(carol);
+    assert_eq!(next_message(&mut alice), ServerMessage::Left { name: String::from("carol") });
+}
+
+#[test]
+fn rooms_race_side_by_side_without_seeing_each_other() {
+    let (_server, address) = start_server();
+    let (mut red, mut red_writer) = join(&address, "red", "ann");
+    let (mut blue, mut blue_writer) = join(&address, "blue", "ben");
+
+    // both rooms play to a win; each player only ever hears about their own room
+    for (reader, writer, name) in [(&mut red, &mut red_writer, "ann"), (&mut blue, &mut blue_writer, "ben")] {
+        let (mut low, mut high) = (1i128, 100i128);
+        let secret = loop {
+            let guess = low + (high - low) / 2;
+            say(writer, ClientMessage::Guess(guess.to_string()));
+            match next_message(reader) {
+                ServerMessage::Guess { name: guesser, ordering, .. } => {
+                    assert_eq!(guesser, name);
+                    match ordering {
+                        Ordering::Less => low = guess + 1,
+                        Ordering::Greater => high = guess - 1,
+                        Ordering::Equal => break guess,
+                    }
+                }
+                other => panic!("{} expected a guess result, got {:?}", name, other),
+            }
+        };
+        assert!(matches!(next_message(reader), ServerMessage::Winner { name: winner, .. } if winner == name));
+        assert!(matches!(next_message(reader), ServerMessage::NewRound { .. }));
+        assert!((1..=100).contains(&secret));
+    }
+
+    // once red is empty, a new player there starts a fresh room rather than joining blue's game
+    say(&mut red_writer, ClientMessage::Quit);
+    let (_, mut late) = join(&address, "red", "cat");
+    say(&mut blue_writer, ClientMessage::Guess(String::from("1")));
+    assert!(matches!(next_message(&mut blue), ServerMessage::Guess { name, .. } if name == "ben"));
+    say(&mut late, ClientMessage::Quit);
+}
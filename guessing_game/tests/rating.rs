@@ -0,0 +1,110 @@
+use guessing_game::game::optimum;
+use guessing_game::rating::{self, game_rating, score, settings_for, Rating, Ratings, START};
+use guessing_game::{Difficulty, Game, Settings};
+
+fn close(actual: f64, expected: f64) -> bool {
+    (actual - expected).abs() < 0.01
+}
+
+// a game on these settings won with the given guesses, the last one being the secret
+fn won(settings: Settings, guesses: &[i128]) -> Game {
+    let mut game = Game::with_secret(settings, *guesses.last().unwrap());
+    for &guess in guesses {
+        game.submit(guess);
+    }
+    game
+}
+
+#[test]
+fn binary_search_needs_one_guess_per_bit() {
+    assert_eq!(optimum(0), 1);
+    assert_eq!(optimum(1), 1);
+    assert_eq!(optimum(8), 4);
+    assert_eq!(optimum(100), 7);
+    assert_eq!(optimum(u128::MAX), 128);
+}
+
+#[test]
+fn games_are_rated_by_range_and_spare_attempts() {
+    let custom = |high, attempts| Settings::new(Difficulty::Custom, 1, high, attempts);
+    assert!(close(game_rating(&Settings::default()), 1000.0));
+    // doubling the range, with the attempts to match, adds 100
+    assert!(close(game_rating(&custom(200, Some(11))), 1100.0));
+    // and so does taking one spare attempt away
+    assert!(close(game_rating(&custom(100, Some(9))), 1100.0));
+    // no limit is as easy as ten spare attempts
+    assert!(close(game_rating(&custom(100, None)), 300.0));
+
+    let full = Settings::new(Difficulty::Custom, i128::MIN, i128::MAX, Some(128));
+    let rating = game_rating(&full);
+    assert!(rating.is_finite() && rating > game_rating(&custom(1 << 60, Some(61))), "{}", rating);
+}
+
+#[test]
+fn a_quick_win_scores_one_and_anything_else_less() {
+    let settings = Settings::default();
+    assert_eq!(score(&won(settings, &[50, 25, 37, 43, 40, 41, 42])), 1.0);
+    assert_eq!(score(&won(settings, &[42])), 1.0);
+    // twice binary search's worst case scores half
+    let unlimited = Settings { max_attempts: None, ..settings };
+    let slow: Vec<i128> = (29..=42).collect();
+    assert_eq!(score(&won(unlimited, &slow)), 0.5);
+
+    let mut lost = Game::with_secret(settings, 42);
+    for guess in 1..=10 {
+        lost.submit(guess);
+    }
+    assert_eq!(score(&lost), 0.0);
+
+    let mut gave_up = Game::with_secret(settings, 42);
+    gave_up.give_up();
+    assert_eq!(score(&gave_up), 0.0);
+
+    let full = Settings::new(Difficulty::Custom, i128::MIN, i128::MAX, None);
+    assert_eq!(score(&won(full, &[i128::MIN, 0])), 1.0);
+}
+
+#[test]
+fn adaptive_games_sit_a_little_below_the_player() {
+    // a new player starts on the normal game
+    assert_eq!(settings_for(START), Settings::new(Difficulty::Custom, 1, 100, Some(10)));
+
+    for rating in [900.0, 1200.0, 1500.0, 2000.0, 3000.0] {
+        let settings = settings_for(rating);
+        assert!((game_rating(&settings) - (rating - 200.0)).abs() < 5.0, "{}: {:?}", rating, settings);
+    }
+
+    // the range stays between 1..=8 and 1..=2^60 however far the rating goes
+    assert_eq!(settings_for(-1e9), Settings::new(Difficulty::Custom, 1, 8, Some(10)));
+    assert_eq!(settings_for(1e9), Settings::new(Difficulty::Custom, 1, 1 << 60, Some(61)));
+}
+
+#[test]
+fn ratings_move_towards_how_the_game_went() {
+    // 200 points above the game, a win is expected about three times in four
+    let expected = rating::expected(START, 1000.0);
+    assert!(close(expected, 0.76), "{}", expected);
+
+    let mut rating = Rating::default();
+    rating.update(&won(Settings::default(), &[50, 25, 37, 43, 40, 41, 42]));
+    assert!(close(rating.rating, START + 32.0 * (1.0 - expected)), "{:?}", rating);
+    assert_eq!(rating.games, 1);
+
+    let mut gave_up = Game::with_secret(Settings::default(), 42);
+    gave_up.give_up();
+    let mut rating = Rating::default();
+    rating.update(&gave_up);
+    assert!(close(rating.rating, START - 32.0 * expected), "{:?}", rating);
+
+    // a full-range win is rated without overflowing
+    let full = Settings::new(Difficulty::Custom, i128::MIN, i128::MAX, None);
+    let mut rating = Rating::default();
+    rating.update(&won(full, &[i128::MAX]));
+    assert!(rating.rating.is_finite() && rating.rating > START, "{:?}", rating);
+
+    let mut ratings = Ratings::default();
+    let (before, after) = ratings.record("ana", &gave_up);
+    assert_eq!(before, Rating::default());
+    assert_eq!(ratings.get("ana"), after);
+    assert_eq!(ratings.get("bo"), Rating::default());
+}
@@ -0,0 +1,95 @@
+use std::cmp::Ordering;
+use guessing_game::reverse::{midpoint, reverse_game, Answer, Contradiction, Guesser};
+use guessing_game::locale::Locale;
+use guessing_game::{Difficulty, Settings};
+
+#[test]
+fn the_midpoint_never_overflows() {
+    assert_eq!(midpoint(1, 100), 50);
+    assert_eq!(midpoint(-3, 0), -2);
+    assert_eq!(midpoint(i128::MIN, i128::MAX), -1);
+    assert_eq!(midpoint(i128::MAX - 1, i128::MAX), i128::MAX - 1);
+    assert_eq!(midpoint(i128::MIN, i128::MIN + 1), i128::MIN);
+}
+
+#[test]
+fn answers_are_understood_in_several_forms() {
+    for (text, answer) in [("h", Answer::Higher), (" Bigger\n", Answer::Higher), ("-", Answer::Lower), ("YES", Answer::Correct)] {
+        assert_eq!(text.parse(), Ok(answer), "{:?}", text);
+    }
+    assert!("maybe".parse::<Answer>().unwrap_err().contains("'maybe' is not an answer"));
+}
+
+// the answers an honest player thinking of number gives, worked out ahead of time
+fn honest_answers(settings: &Settings, number: i128) -> Vec<&'static str> {
+    let mut guesser = Guesser::new(settings);
+    let mut answers = Vec::new();
+    while guesser.found().is_none() {
+        let guess = guesser.next_guess();
+        let answer = match number.cmp(&guess) {
+            Ordering::Greater => Answer::Higher,
+            Ordering::Less => Answer::Lower,
+            Ordering::Equal => Answer::Correct,
+        };
+        guesser.answer(guess, answer).unwrap();
+        answers.push(match answer {
+            Answer::Higher => "higher",
+            Answer::Lower => "lower",
+            Answer::Correct => "correct",
+        });
+    }
+    answers
+}
+
+#[test]
+fn the_computer_finds_any_number_in_the_fewest_guesses() {
+    let settings = Settings::new(Difficulty::Custom, 1, 100, None);
+    for number in 1..=100 {
+        let answers = honest_answers(&settings, number);
+        let mut output = Vec::new();
+        let guesses = reverse_game(answers.join("\n").as_bytes(), &mut output, settings, Locale::En).unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert_eq!(guesses, Some(answers.len() as u32));
+        assert!(answers.len() <= 7, "{} took {} guesses", number, answers.len());
+        assert!(output.ends_with(&format!("Your number is {}. I needed {} guesses.\n", number, answers.len())), "{}", output);
+    }
+
+    let wide = Settings::new(Difficulty::Custom, i128::MIN, i128::MAX, None);
+    for number in [i128::MIN, -1, 0, i128::MAX] {
+        assert!(honest_answers(&wide, number).len() <= 129);
+    }
+}
+
+#[test]
+fn contradicting_answers_are_caught_and_asked_again() {
+    let settings = Settings::new(Difficulty::Custom, 1, 100, None);
+    let mut guesser = Guesser::new(&settings);
+    guesser.answer(50, Answer::Lower).unwrap();
+    guesser.answer(25, Answer::Higher).unwrap();
+    assert_eq!(
+        guesser.answer(60, Answer::Correct),
+        Err(Contradiction { guess: 60, answer: Answer::Correct, conflict: Some((1, 50, Answer::Lower)) })
+    );
+    assert_eq!(guesser.answers().len(), 2);
+
+    let mut edge = Guesser::new(&settings);
+    let contradiction = edge.answer(1, Answer::Lower).unwrap_err();
+    assert_eq!(contradiction.conflict, None);
+    assert!(contradiction.to_string().contains("outside the range"));
+
+    // the game repeats the question after a contradiction or a line it can't read
+    let mut output = Vec::new();
+    let answers = "lower\nhigher\nhmm\nlower\ncorrect\n";
+    let small = Settings::new(Difficulty::Custom, 1, 3, None);
+    let guesses = reverse_game(answers.as_bytes(), &mut output, small, Locale::En).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert_eq!(guesses, Some(2));
+    assert!(output.contains("'higher' for 1 contradicts answer #1, where you said your number was lower than 2."), "{}", output);
+    assert_eq!(output.matches("Is it 1?").count(), 3, "{}", output);
+
+    // leaving before the number is found is not a win
+    let mut output = Vec::new();
+    assert_eq!(reverse_game(":quit\n".as_bytes(), &mut output, settings, Locale::En).unwrap(), None);
+    assert_eq!(reverse_game("".as_bytes(), &mut output, settings, Locale::En).unwrap(), None);
+}
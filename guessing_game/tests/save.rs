@@ -0,0 +1,121 @@
+use std::fs;
+use std::io::{Cursor, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use guessing_game::save::SavedGame;
+use guessing_game::storage::fnv1a;
+use guessing_game::{play_game, Game, Session, Settings, Status};
+
+fn temp_dir(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("guessing_game-save-{}-{}", name, std::process::id()))
+}
+
+fn saved() -> SavedGame {
+    SavedGame {
+        seed: 7,
+        settings: Settings::default(),
+        secret: 42,
+        guesses: vec![50, 25],
+        elapsed_ms: 1500,
+    }
+}
+
+// rewrites the save file through its JSON, the way someone editing it by hand would
+fn edit(path: &Path, change: impl FnOnce(&mut serde_json::Value)) {
+    let mut file: serde_json::Value = serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+    change(&mut file);
+    fs::write(path, file.to_string()).unwrap();
+}
+
+fn load_error(path: &Path) -> String {
+    let error = SavedGame::load(path).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    error.to_string()
+}
+
+#[test]
+fn a_saved_game_resumes_where_it_was_left() {
+    let dir = temp_dir("resume");
+    let path = dir.join("nested").join("save.json");
+    let session = Session {
+        seed: 7,
+        save_path: Some(path.clone()),
+        ..Session::default()
+    };
+
+    let mut output = Vec::new();
+    let mut game = Game::with_secret(Settings::default(), 42);
+    let result = play_game(Cursor::new("50\n25\n:save\n"), &mut output, &mut game, &session).unwrap();
+    assert_eq!(result.status, Status::Quit);
+
+    let loaded = SavedGame::load(&path).unwrap();
+    assert_eq!(loaded.seed, 7);
+    assert_eq!(loaded.secret, 42);
+    assert_eq!(loaded.guesses, [50, 25]);
+
+    let mut game = loaded.restore();
+    assert_eq!(game.attempts(), 2);
+    let session = Session { elapsed: loaded.elapsed(), ..Session::default() };
+    let result = play_game(Cursor::new("42\n"), &mut Vec::new(), &mut game, &session).unwrap();
+    assert_eq!(result.status, Status::Won);
+    assert_eq!(result.attempts, 3);
+    assert!(result.elapsed >= loaded.elapsed());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn edited_saves_are_refused() {
+    let dir = temp_dir("tampered");
+    let path = dir.join("save.json");
+    saved().save(&path).unwrap();
+    assert_eq!(SavedGame::load(&path).unwrap(), saved());
+    assert_eq!(saved().elapsed(), Duration::from_millis(1500));
+
+    // forgetting a guess, or peeking at a different secret, breaks the checksum
+    edit(&path, |file| file["game"]["guesses"] = serde_json::json!([50]));
+    assert_eq!(load_error(&path), "the save has been tampered with");
+
+    saved().save(&path).unwrap();
+    edit(&path, |file| file["game"]["secret"] = serde_json::json!(43));
+    assert_eq!(load_error(&path), "the save has been tampered with");
+
+    saved().save(&path).unwrap();
+    edit(&path, |file| file["checksum"] = serde_json::json!("0000000000000000"));
+    assert_eq!(load_error(&path), "the save has been tampered with");
+
+    saved().save(&path).unwrap();
+    edit(&path, |file| file["version"] = serde_json::json!(99));
+    assert_eq!(load_error(&path), "save version 99 is not supported");
+
+    fs::write(&path, "not json").unwrap();
+    load_error(&path);
+    assert_eq!(SavedGame::load(&dir.join("missing.json")).unwrap_err().kind(), ErrorKind::NotFound);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn saves_that_make_no_sense_are_refused_even_with_a_good_checksum() {
+    let dir = temp_dir("nonsense");
+    let path = dir.join("save.json");
+
+    SavedGame { secret: 500, ..saved() }.save(&path).unwrap();
+    assert_eq!(load_error(&path), "the saved secret is outside the saved range");
+
+    SavedGame { guesses: vec![50, 42], ..saved() }.save(&path).unwrap();
+    assert_eq!(load_error(&path), "the saved game is already over");
+
+    let backwards = Settings { low: 100, high: 1, ..Settings::default() };
+    SavedGame { settings: backwards, ..saved() }.save(&path).unwrap();
+    load_error(&path);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn checksums_use_the_standard_fnv_1a() {
+    assert_eq!(fnv1a(&[]), 0xcbf2_9ce4_8422_2325);
+    assert_eq!(fnv1a(&[b"a"]), 0xaf63_dc4c_8601_ec8c);
+    assert_eq!(fnv1a(&[b"foo", b"bar"]), fnv1a(&[b"foobar"]));
+}
@@ -0,0 +1,64 @@
+mod harness;
+
+use std::fs;
+use std::path::Path;
+use std::process::Output;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use guessing_game::cli::{parse_args, resolve_seed, SEED_ENV};
+use guessing_game::{Game, Settings};
+
+fn args(line: &str) -> Vec<String> {
+    line.split_whitespace().map(String::from).collect()
+}
+
+#[test]
+fn the_seed_is_read_from_either_form_of_the_flag() {
+    assert_eq!(parse_args(args("--seed 42")).unwrap().seed, Some(42));
+    assert_eq!(parse_args(args("--seed=42")).unwrap().seed, Some(42));
+    assert_eq!(parse_args(args("")).unwrap().seed, None);
+    assert_eq!(parse_args(args("--seed")).unwrap_err(), "--seed needs a value");
+    assert_eq!(parse_args(args("--seed -1")).unwrap_err(), "invalid seed: -1");
+
+    // a seed given on the command line is used as it is, whatever the environment says
+    let options = parse_args(args("--seed 7")).unwrap();
+    assert_eq!(resolve_seed(&options), Ok(7));
+}
+
+#[test]
+fn the_same_seed_picks_the_same_secret() {
+    let secrets = |seed: u64| -> Vec<i128> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..5).map(|_| Game::new(Settings::default(), &mut rng).secret()).collect()
+    };
+    assert_eq!(secrets(42), secrets(42));
+    assert_ne!(secrets(42), secrets(43));
+}
+
+// runs the binary with no input, so the game ends at once and prints its secret
+fn run(dir: &Path, seed_flag: Option<&str>, seed_env: Option<&str>) -> Output {
+    let mut command = harness::command(dir, &[]);
+    if let Some(seed) = seed_flag {
+        command.args(["--seed", seed]);
+    }
+    if let Some(seed) = seed_env {
+        command.env(SEED_ENV, seed);
+    }
+    harness::run_with(command, "")
+}
+
+#[test]
+fn the_flag_wins_over_the_environment() {
+    let dir = harness::temp_dir("seed");
+    let from_flag = run(&dir, Some("42"), None);
+    let from_env = run(&dir, None, Some("42"));
+    let both = run(&dir, Some("42"), Some("9"));
+    assert!(String::from_utf8_lossy(&from_flag.stdout).starts_with("Seed: 42\n"));
+    assert_eq!(from_flag.stdout, from_env.stdout);
+    assert_eq!(from_flag.stdout, both.stdout);
+
+    let broken = run(&dir, None, Some("soon"));
+    assert_eq!(broken.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&broken.stderr).contains("invalid seed: soon (GUESSING_GAME_SEED)"));
+    let _ = fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,97 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use guessing_game::simulate::{simulate, Stats, MAX_ATTEMPTS};
+use guessing_game::strategy::{self, BinarySearch, Linear, Strategy};
+use guessing_game::{Difficulty, Game, Settings, Status};
+
+#[test]
+fn stats_sum_up_the_attempts() {
+    let stats = Stats::from_attempts("test", vec![4, 1, 3, 4]);
+    assert_eq!(stats.games, 4);
+    assert_eq!(stats.mean, 3.0);
+    assert_eq!(stats.median, 3.5);
+    assert_eq!((stats.min, stats.max), (1, 4));
+    assert_eq!(stats.histogram.into_iter().collect::<Vec<_>>(), [(1, 1), (3, 1), (4, 2)]);
+
+    let empty = Stats::from_attempts("none", Vec::new());
+    assert_eq!((empty.games, empty.mean, empty.median, empty.min, empty.max), (0, 0.0, 0.0, 0, 0));
+
+    let mut output = Vec::new();
+    Stats::from_attempts("test", vec![1, 2, 2]).print(&mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.starts_with("test: 3 games, mean 1.67, median 2.0, min 1, max 2\n"), "{}", output);
+    assert!(output.contains("          2 | ######################################## 2\n"), "{}", output);
+}
+
+#[test]
+fn every_strategy_wins_and_binary_search_is_never_beaten_at_worst() {
+    let settings = Settings::new(Difficulty::Custom, 1, 100, Some(10));
+    for secret in 1..=100 {
+        let mut game = Game::with_secret(settings, secret);
+        assert_eq!(strategy::play(&mut BinarySearch, &mut game, &mut StdRng::seed_from_u64(0)), Status::Won);
+        assert!(game.attempts() <= 7);
+    }
+
+    let mut game = Game::with_secret(Settings::new(Difficulty::Custom, 1, 100, None), 37);
+    strategy::play(&mut Linear, &mut game, &mut StdRng::seed_from_u64(0));
+    assert_eq!(game.attempts(), 37);
+
+    let stats = simulate(&mut strategy::all(), Settings::default(), 200, 5);
+    let names: Vec<&str> = stats.iter().map(|stats| stats.name).collect();
+    assert_eq!(names, ["binary", "golden", "random", "linear", "bad"]);
+    for stats in &stats {
+        assert_eq!(stats.games, 200);
+        assert_eq!(stats.unfinished, 0, "{}", stats.name);
+    }
+    assert!(stats[0].max <= 7);
+    assert!(stats.iter().all(|other| other.max >= stats[0].max));
+
+    for name in names {
+        assert_eq!(strategy::by_name(&name.to_uppercase()).unwrap().name(), name);
+    }
+    assert!(strategy::by_name("psychic").is_err());
+}
+
+#[test]
+fn the_same_seed_gives_the_same_results() {
+    let settings = Settings::new(Difficulty::Custom, -500, 500, None);
+    let first = simulate(&mut strategy::all(), settings, 100, 123);
+    let second = simulate(&mut strategy::all(), settings, 100, 123);
+    assert_eq!(first, second);
+
+    let other = simulate(&mut strategy::all(), settings, 100, 124);
+    assert_ne!(first, other);
+}
+
+#[test]
+fn slow_strategies_are_stopped_on_huge_ranges() {
+    let settings = Settings::new(Difficulty::Custom, 0, u64::MAX as i128, None);
+    let mut strategies: Vec<Box<dyn Strategy>> = vec![Box::new(BinarySearch), Box::new(Linear)];
+    let stats = simulate(&mut strategies, settings, 10, 1);
+
+    assert_eq!(stats[0].unfinished, 0);
+    assert!(stats[0].max <= 65);
+    assert_eq!(stats[1].unfinished, 10);
+    assert_eq!(stats[1].max, MAX_ATTEMPTS);
+
+    let mut output = Vec::new();
+    stats[1].print(&mut output).unwrap();
+    assert!(String::from_utf8(output).unwrap().contains("10 games were stopped after 10000 attempts"));
+}
+
+#[test]
+fn the_halving_strategies_stay_logarithmic_far_from_zero() {
+    let far = 1_000_000_000_000_000_000_000_000_000_000;
+    for (settings, bits) in [
+        (Settings::new(Difficulty::Custom, far, far + 1000, None), 10),
+        (Settings::new(Difficulty::Custom, i128::MIN, i128::MAX, None), 128),
+    ] {
+        let stats = simulate(&mut strategy::all(), settings, 20, 7);
+        for stats in &stats[..2] {
+            assert_eq!(stats.unfinished, 0, "{}", stats.name);
+        }
+        assert!(stats[0].max <= bits, "binary took {}", stats[0].max);
+        // golden section cuts off at least 38% each time, so it needs at most 1.44 times as many
+        assert!(stats[1].max <= bits * 3 / 2 + 2, "golden took {}", stats[1].max);
+    }
+}
@@ -0,0 +1,108 @@
+use guessing_game::history::{Ending, GameRecord};
+use guessing_game::stats::PlayerStats;
+use guessing_game::Difficulty;
+
+// 2024-01-01, a monday
+const NEW_YEAR: u64 = 19_723;
+
+// a game played at noon on the given day
+fn game(day: u64, ending: Ending, attempts: u32, optimal: u32) -> GameRecord {
+    GameRecord {
+        player: String::from("ana"),
+        timestamp: day * 86_400 + 12 * 3600,
+        difficulty: Difficulty::Normal,
+        low: 1,
+        high: 100,
+        ending,
+        secret: 42,
+        guesses: (0..attempts as i128).collect(),
+        optimal,
+        duration_ms: 1000,
+    }
+}
+
+fn stats(games: &[GameRecord]) -> PlayerStats {
+    PlayerStats::from_games("ana", &games.iter().collect::<Vec<_>>())
+}
+
+#[test]
+fn games_are_grouped_into_weeks_starting_on_monday() {
+    let games = [
+        game(NEW_YEAR, Ending::Won, 4, 3),
+        game(NEW_YEAR + 6, Ending::Lost, 10, 5),
+        game(NEW_YEAR + 7, Ending::Won, 2, 4),
+    ];
+    let weeks = stats(&games).weeks;
+    let starts: Vec<&str> = weeks.iter().map(|week| week.start.as_str()).collect();
+    assert_eq!(starts, ["2024-01-01", "2024-01-08"]);
+    assert_eq!((weeks[0].games, weeks[0].wins, weeks[0].extra), (2, 1, Some(1.0)));
+    assert_eq!((weeks[1].games, weeks[1].wins, weeks[1].extra), (1, 1, Some(-2.0)));
+
+    // only the latest eight weeks are shown
+    let games: Vec<GameRecord> = (0..10).map(|week| game(NEW_YEAR + 7 * week, Ending::GaveUp, 1, 7)).collect();
+    let weeks = stats(&games).weeks;
+    assert_eq!(weeks.len(), 8);
+    assert_eq!(weeks[0].start, "2024-01-15");
+    assert_eq!(weeks[0].extra, None);
+}
+
+#[test]
+fn the_first_days_of_the_epoch_share_a_week() {
+    // thursday 1970-01-01 to sunday 1970-01-04 come before the first monday
+    let games: Vec<GameRecord> = (0..6).map(|day| game(day, Ending::Won, 3, 3)).collect();
+    let weeks = stats(&games).weeks;
+    assert_eq!(weeks.len(), 2);
+    assert_eq!((weeks[0].start.as_str(), weeks[0].games), ("1970-01-01", 4));
+    assert_eq!((weeks[1].start.as_str(), weeks[1].games), ("1970-01-05", 2));
+}
+
+#[test]
+fn totals_are_printed_with_the_weekly_table() {
+    let games = [
+        game(NEW_YEAR, Ending::Won, 4, 3),
+        game(NEW_YEAR, Ending::Lost, 10, 5),
+        game(NEW_YEAR + 7, Ending::Won, 2, 4),
+    ];
+    let mut output = Vec::new();
+    stats(&games).print(&mut output).unwrap();
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "Stats for ana:\n\
+         \x20 games played      3\n\
+         \x20 wins              2 (67%)\n\
+         \x20 average attempts  3.0 per win\n\
+         \x20 best              2 attempts on 1..=100\n\
+         \x20 vs binary search  3.5 per win on the same secrets; you took 0.5 fewer\n\
+         \x20 by week:\n\
+         \x20   2024-01-01    2 games    1 won  +1.0 guesses vs binary search\n\
+         \x20   2024-01-08    1 games    1 won  -2.0 guesses vs binary search\n"
+    );
+
+    let mut output = Vec::new();
+    stats(&[]).print(&mut output).unwrap();
+    assert_eq!(String::from_utf8(output).unwrap(), "Stats for ana:\n  games played      0\n  wins              0 (0%)\n");
+}
+
+#[test]
+fn the_trend_compares_recent_wins_with_earlier_ones() {
+    let trend = |extras: &[u32]| {
+        let games: Vec<GameRecord> = extras.iter().map(|&extra| game(NEW_YEAR, Ending::Won, 3 + extra, 3)).collect();
+        let stats = stats(&games);
+        let mut output = Vec::new();
+        stats.print(&mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let line = output.lines().find(|line| line.starts_with("  trend")).map(String::from);
+        (stats.trend, line)
+    };
+
+    let (change, line) = trend(&[3, 3, 0, 0]);
+    assert_eq!(change, Some(-3.0));
+    assert_eq!(line.unwrap(), "  trend             improving: recent wins take 3.0 fewer guesses");
+
+    let (change, line) = trend(&[0, 1, 2, 1, 3]);
+    assert_eq!(change, Some(1.5));
+    assert_eq!(line.unwrap(), "  trend             slipping: recent wins take 1.5 more guesses");
+
+    assert_eq!(trend(&[1, 1, 1, 1]).1.unwrap(), "  trend             steady");
+    assert_eq!(trend(&[9, 0, 0]), (None, None));
+}
@@ -0,0 +1,60 @@
+use std::io::Cursor;
+use guessing_game::locale::Locale;
+use guessing_game::temperature::{feedback, Temperature, Trend};
+use guessing_game::{play_game, Difficulty, Game, Session, Settings};
+
+#[test]
+fn the_temperature_scales_with_the_range() {
+    assert_eq!(Temperature::of(1, 10), Temperature::Burning);
+    assert_eq!(Temperature::of(2, 100), Temperature::Burning);
+    assert_eq!(Temperature::of(5, 100), Temperature::Hot);
+    assert_eq!(Temperature::of(15, 100), Temperature::Warm);
+    assert_eq!(Temperature::of(35, 100), Temperature::Cold);
+    assert_eq!(Temperature::of(36, 100), Temperature::Freezing);
+
+    // the same fraction of a much bigger range is just as warm
+    assert_eq!(Temperature::of(150_000, 1_000_000), Temperature::Warm);
+    assert_eq!(Temperature::of(u128::MAX / 2, u128::MAX), Temperature::Freezing);
+    assert_eq!(Temperature::of(0, 0), Temperature::Burning);
+
+    assert_eq!(Trend::between(10, 5), Trend::Warmer);
+    assert_eq!(Trend::between(5, 10), Trend::Colder);
+    assert_eq!(Trend::between(5, 5), Trend::Same);
+}
+
+#[test]
+fn each_guess_after_the_first_says_whether_it_got_warmer() {
+    let mut game = Game::with_secret(Settings::default(), 50);
+    assert_eq!(feedback(&game, Locale::En), None);
+
+    game.submit(90);
+    assert_eq!(feedback(&game, Locale::En).as_deref(), Some("Freezing."));
+    game.submit(60);
+    assert_eq!(feedback(&game, Locale::En).as_deref(), Some("Warm. Warmer than your last guess."));
+    game.submit(40);
+    assert_eq!(feedback(&game, Locale::En).as_deref(), Some("Warm. Just as far as your last guess."));
+    game.submit(1);
+    assert_eq!(feedback(&game, Locale::En).as_deref(), Some("Freezing. Colder than your last guess."));
+    game.submit(50);
+    assert_eq!(feedback(&game, Locale::En), None);
+
+    // the widest range doesn't overflow the distance
+    let mut game = Game::with_secret(Settings::new(Difficulty::Custom, i128::MIN, i128::MAX, None), i128::MAX);
+    game.submit(i128::MIN);
+    assert_eq!(feedback(&game, Locale::En).as_deref(), Some("Freezing."));
+}
+
+#[test]
+fn hot_cold_games_print_the_feedback_and_plain_ones_do_not() {
+    let play = |temperature: bool| {
+        let settings = Settings { temperature, ..Settings::default() };
+        let mut output = Vec::new();
+        play_game(Cursor::new("90\n52\n50\n"), &mut output, &mut Game::with_secret(settings, 50), &Session::default()).unwrap();
+        String::from_utf8(output).unwrap()
+    };
+
+    let output = play(true);
+    assert!(output.contains("Too big!\nFreezing.\n"), "{}", output);
+    assert!(output.contains("Too big!\nBurning! Warmer than your last guess.\n"), "{}", output);
+    assert!(!play(false).contains("Freezing."));
+}
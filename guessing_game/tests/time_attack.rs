@@ -0,0 +1,157 @@
+mod harness;
+
+use std::collections::VecDeque;
+use std::io::{self, BufRead, Read};
+use std::process::Stdio;
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use guessing_game::clock::ManualClock;
+use guessing_game::locale::Locale;
+use guessing_game::timeattack::time_attack;
+use guessing_game::{Difficulty, Game, Settings};
+
+// a player who takes the same time over every line they type
+struct Typist {
+    lines: VecDeque<String>,
+    current: Vec<u8>,
+    clock: Arc<ManualClock>,
+    per_line: Duration,
+}
+
+impl Typist {
+    fn new(lines: Vec<String>, clock: Arc<ManualClock>, per_line: Duration) -> Typist {
+        Typist {
+            lines: lines.into(),
+            current: Vec::new(),
+            clock,
+            per_line,
+        }
+    }
+}
+
+impl Read for Typist {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl BufRead for Typist {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.current.is_empty() {
+            if let Some(line) = self.lines.pop_front() {
+                self.clock.advance(self.per_line);
+                self.current = format!("{}\n", line).into_bytes();
+            }
+        }
+        Ok(&self.current)
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.current.drain(..amount);
+    }
+}
+
+#[test]
+fn guesses_after_the_buzzer_do_not_count() {
+    let settings = Settings::new(Difficulty::Custom, 1, 100, None);
+
+    // a second rng from the same seed deals out the same secrets, so the player can know them
+    let mut peek = StdRng::seed_from_u64(3);
+    let secrets: Vec<String> = (0..5)
+        .map(|_| Game::new(settings, &mut peek).secret().to_string())
+        .collect();
+
+    // four seconds a line against a ten second budget: the third answer comes in at twelve
+    let clock = Arc::new(ManualClock::new());
+    let typist = Typist::new(secrets, Arc::clone(&clock), Duration::from_secs(4));
+
+    let mut output = Vec::new();
+    let mut rng = StdRng::seed_from_u64(3);
+    let score = time_attack(typist, &mut output, settings, Duration::from_secs(10), &*clock, &mut rng, Locale::En)
+        .unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    assert_eq!(score.solved, 2);
+    assert_eq!(score.guesses, 2);
+    assert!(score.finished);
+    assert!(output.contains("[10.0s left] Please input your guess."));
+    assert!(output.contains("[6.0s left] Please input your guess."));
+    assert!(output.contains("[2.0s left] Please input your guess."));
+    assert!(output.contains("Time's up!"));
+    assert!(output.contains("Final score: 2 found, 0 missed, 2 guesses."));
+}
+
+// a player who never types anything, until the test is over and the input closes
+struct Silence(Receiver<()>);
+
+impl Read for Silence {
+    fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+        let _ = self.0.recv();
+        Ok(0)
+    }
+}
+
+impl BufRead for Silence {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        let _ = self.0.recv();
+        Ok(&[])
+    }
+
+    fn consume(&mut self, _: usize) {}
+}
+
+#[test]
+fn the_round_ends_when_the_time_does_even_without_a_guess() {
+    let settings = Settings::new(Difficulty::Custom, 1, 100, None);
+    let (hang_up, silence) = mpsc::channel();
+
+    let clock = Arc::new(ManualClock::new());
+    let buzzer = {
+        let clock = Arc::clone(&clock);
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(100));
+            clock.advance(Duration::from_secs(31));
+        })
+    };
+
+    let mut output = Vec::new();
+    let mut rng = StdRng::seed_from_u64(3);
+    let budget = Duration::from_secs(30);
+    let score = time_attack(Silence(silence), &mut output, settings, budget, &*clock, &mut rng, Locale::En).unwrap();
+    buzzer.join().unwrap();
+    drop(hang_up);
+    let output = String::from_utf8(output).unwrap();
+
+    assert_eq!(score.solved, 0);
+    assert_eq!(score.guesses, 0);
+    assert!(score.finished);
+    assert!(output.contains("[30.0s left] Please input your guess."), "{}", output);
+    assert!(output.contains("Time's up!"), "{}", output);
+    assert!(output.contains("Final score: 0 found, 0 missed, 0 guesses."), "{}", output);
+}
+
+#[test]
+fn running_out_of_time_without_finding_anything_is_a_loss() {
+    // stdin is left open and silent, so only the clock can end the run
+    let dir = harness::temp_dir("time-attack");
+    let mut child = harness::command(&dir, &["timeattack", "--time", "1", "--seed", "1"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let stdin = child.stdin.take();
+    let result = child.wait_with_output().unwrap();
+    drop(stdin);
+
+    assert_eq!(result.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&result.stdout).contains("Time's up!"));
+    let _ = std::fs::remove_dir_all(&dir);
+}
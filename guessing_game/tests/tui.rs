@@ -0,0 +1,28 @@
+#![cfg(feature = "tui")]
+
+use guessing_game::tui::interval_bar;
+
+fn filled(bar: &str) -> Vec<usize> {
+    bar.chars().enumerate().filter(|&(_, cell)| cell == '█').map(|(i, _)| i).collect()
+}
+
+#[test]
+fn the_bar_fills_the_part_of_the_range_still_possible() {
+    assert_eq!(interval_bar(10, 1, 100, 1, 100), "██████████");
+    assert_eq!(interval_bar(10, 1, 100, 1, 50), "█████░░░░░");
+    assert_eq!(interval_bar(10, 1, 100, 51, 100), "░░░░░█████");
+    assert_eq!(interval_bar(10, 1, 100, 34, 41), "░░░██░░░░░");
+    assert_eq!(interval_bar(0, 1, 100, 1, 100), "");
+}
+
+#[test]
+fn a_single_number_always_gets_a_cell() {
+    assert_eq!(interval_bar(10, 1, 1000, 500, 500), "░░░░█░░░░░");
+    assert_eq!(filled(&interval_bar(10, 1, 1000, 1, 1)), [0]);
+    assert_eq!(filled(&interval_bar(10, 1, 1000, 1000, 1000)), [9]);
+
+    // at the ends of the widest range floats round the last number onto the edge of the bar
+    assert_eq!(filled(&interval_bar(40, i128::MIN, i128::MAX, i128::MIN, i128::MIN)), [0]);
+    assert_eq!(filled(&interval_bar(40, i128::MIN, i128::MAX, i128::MAX, i128::MAX)), [39]);
+    assert_eq!(interval_bar(4, i128::MIN, i128::MAX, i128::MIN, i128::MAX), "████");
+}